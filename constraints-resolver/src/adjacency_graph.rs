@@ -0,0 +1,333 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::geometry::Rectangle;
+use crate::instances::expand_instances;
+use crate::types::{AdjacencyRelation, AdjacencyStrength, Room, RoomRequirement};
+
+/// Two rooms that share a wall in a layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdjacencyEdge {
+    pub room_id: String,
+    pub other_room_id: String,
+    pub shared_wall_length: f64,
+}
+
+/// The adjacency graph a layout actually realises.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdjacencyGraph {
+    /// Room ids in layout order.
+    pub nodes: Vec<String>,
+    pub edges: Vec<AdjacencyEdge>,
+}
+
+/// An adjacency relation from the requirements, with how much wall the layout gives it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestedAdjacency {
+    pub room_id: String,
+    pub other_room_id: String,
+    pub strength: AdjacencyStrength,
+    /// 0.0 when the rooms do not share a wall.
+    pub shared_wall_length: f64,
+}
+
+/// The realised adjacency graph checked against the requested one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdjacencyReport {
+    pub graph: AdjacencyGraph,
+    /// Requested adjacencies (`Required`, `StronglyPreferred`, `Preferred`) the layout realises.
+    pub satisfied: Vec<RequestedAdjacency>,
+    /// Requested adjacencies the layout does not realise.
+    pub missing: Vec<RequestedAdjacency>,
+    /// `Avoid` and `Forbidden` pairs that share a wall anyway.
+    pub forbidden_present: Vec<RequestedAdjacency>,
+}
+
+/// Build the adjacency graph of a layout, one edge per pair of rooms sharing a wall.
+pub fn realised_adjacency(rooms: &[Room]) -> AdjacencyGraph {
+    let mut edges = Vec::new();
+    for (i, room) in rooms.iter().enumerate() {
+        let rect = Rectangle::from_room(room);
+        for other in rooms.iter().skip(i + 1) {
+            let shared_wall_length = rect.shared_edge_length(&Rectangle::from_room(other));
+            if shared_wall_length > 0.0 {
+                edges.push(AdjacencyEdge {
+                    room_id: room.id.clone(),
+                    other_room_id: other.id.clone(),
+                    shared_wall_length,
+                });
+            }
+        }
+    }
+
+    AdjacencyGraph { nodes: rooms.iter().map(|room| room.id.clone()).collect(), edges }
+}
+
+/// Compare the realised adjacency graph of a layout with the requested one.
+///
+/// Requirements are expanded into instances first, so the original
+/// requirements can be passed alongside the solved rooms. Each pair of placed
+/// rooms is reported once, with the relations of both rooms considered: a
+/// shared wall is `forbidden_present` when either room avoids or forbids the
+/// other, and is otherwise credited to the strongest requested relation.
+/// Pairs involving rooms that were not placed are left out.
+pub fn adjacency_report(rooms: &[Room], room_requirements: &[RoomRequirement]) -> AdjacencyReport {
+    let graph = realised_adjacency(rooms);
+    let is_placed = |room_id: &str| rooms.iter().any(|room| room.id == room_id);
+    let wall_between = |a: &str, b: &str| {
+        graph
+            .edges
+            .iter()
+            .find(|e| (e.room_id == a && e.other_room_id == b) || (e.room_id == b && e.other_room_id == a))
+            .map_or(0.0, |e| e.shared_wall_length)
+    };
+
+    let mut relations: Vec<(String, AdjacencyRelation)> = Vec::new();
+    for room_req in expand_instances(room_requirements).iter().filter(|r| is_placed(&r.id)) {
+        for relation in room_req.adjacency_relations() {
+            if is_placed(&relation.room_id) {
+                relations.push((room_req.id.clone(), relation));
+            }
+        }
+    }
+
+    let mut report = AdjacencyReport {
+        graph: graph.clone(),
+        satisfied: Vec::new(),
+        missing: Vec::new(),
+        forbidden_present: Vec::new(),
+    };
+    let mut seen: Vec<(String, String)> = Vec::new();
+    for (room_id, relation) in relations.iter() {
+        let same_pair =
+            |a: &str, b: &str| (a == room_id && b == relation.room_id) || (a == relation.room_id && b == room_id);
+        if seen.iter().any(|(a, b)| same_pair(a, b)) {
+            continue;
+        }
+        seen.push((room_id.clone(), relation.room_id.clone()));
+
+        let pair_relations: Vec<&(String, AdjacencyRelation)> =
+            relations.iter().filter(|(owner, other)| same_pair(owner, &other.room_id)).collect();
+        let with_strength = |strengths: &[AdjacencyStrength]| {
+            strengths.iter().find_map(|strength| pair_relations.iter().find(|(_, r)| r.strength == *strength))
+        };
+        let avoided = with_strength(&[AdjacencyStrength::Forbidden, AdjacencyStrength::Avoid]);
+        let requested = with_strength(&[
+            AdjacencyStrength::Required,
+            AdjacencyStrength::StronglyPreferred,
+            AdjacencyStrength::Preferred,
+        ]);
+        let entry = |(owner, relation): &(String, AdjacencyRelation)| RequestedAdjacency {
+            room_id: owner.clone(),
+            other_room_id: relation.room_id.clone(),
+            strength: relation.strength,
+            shared_wall_length: wall_between(owner, &relation.room_id),
+        };
+
+        let adjacent = wall_between(room_id, &relation.room_id) > 0.0;
+        match (avoided, requested) {
+            (Some(avoided), _) if adjacent => report.forbidden_present.push(entry(avoided)),
+            (_, Some(requested)) if adjacent => report.satisfied.push(entry(requested)),
+            (_, Some(requested)) => report.missing.push(entry(requested)),
+            _ => {}
+        }
+    }
+    report
+}
+
+impl AdjacencyReport {
+    /// Graphviz DOT rendering for design reviews.
+    ///
+    /// Realised edges are labelled with their shared wall length and coloured
+    /// green when requested or red when forbidden; missing edges are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph adjacency {\n");
+        for node in self.graph.nodes.iter() {
+            let _ = writeln!(dot, "    \"{}\";", dot_id(node));
+        }
+
+        let same_pair = |edge: &AdjacencyEdge, requested: &RequestedAdjacency| {
+            (edge.room_id == requested.room_id && edge.other_room_id == requested.other_room_id)
+                || (edge.room_id == requested.other_room_id && edge.other_room_id == requested.room_id)
+        };
+        for edge in self.graph.edges.iter() {
+            let color = if self.satisfied.iter().any(|r| same_pair(edge, r)) {
+                "green"
+            } else if self.forbidden_present.iter().any(|r| same_pair(edge, r)) {
+                "red"
+            } else {
+                "black"
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -- \"{}\" [label=\"{:.2} m\", color={}];",
+                dot_id(&edge.room_id),
+                dot_id(&edge.other_room_id),
+                edge.shared_wall_length,
+                color
+            );
+        }
+        for missing in self.missing.iter() {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -- \"{}\" [label=\"missing\", style=dashed, color=gray];",
+                dot_id(&missing.room_id),
+                dot_id(&missing.other_room_id)
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// JSON rendering with the graph and the comparison.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Escape a room id for use inside a quoted DOT identifier.
+fn dot_id(room_id: &str) -> String {
+    room_id.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    fn layout() -> Vec<Room> {
+        vec![
+            room("living", 0.0, 0.0, 6.0, 4.0),
+            room("kitchen", 6.0, 0.0, 4.0, 3.0),
+            room("bath", 6.0, 3.0, 4.0, 3.0),
+            room("bed", 0.0, 4.0, 6.0, 2.0),
+        ]
+    }
+
+    // Test 1: test_realised_graph_has_shared_wall_lengths
+    #[test]
+    fn test_realised_graph_has_shared_wall_lengths() {
+        let graph = realised_adjacency(&layout());
+
+        let summary: Vec<(&str, &str, f64)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.room_id.as_str(), e.other_room_id.as_str(), e.shared_wall_length))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("living", "kitchen", 3.0),
+                ("living", "bath", 1.0),
+                ("living", "bed", 6.0),
+                ("kitchen", "bath", 4.0),
+                ("bath", "bed", 2.0),
+            ]
+        );
+    }
+
+    // Test 2: test_report_compares_with_requested_graph
+    #[test]
+    fn test_report_compares_with_requested_graph() {
+        let room_requirements = vec![
+            RoomRequirement {
+                id: "living".to_string(),
+                min_area: 20.0,
+                adjacent_to: vec!["kitchen".to_string()],
+                not_adjacent_to: vec!["bath".to_string()],
+                ..Default::default()
+            },
+            RoomRequirement {
+                id: "kitchen".to_string(),
+                min_area: 12.0,
+                adjacent_to: vec!["bed".to_string()],
+                ..Default::default()
+            },
+            RoomRequirement { id: "bath".to_string(), min_area: 12.0, ..Default::default() },
+            RoomRequirement { id: "bed".to_string(), min_area: 12.0, ..Default::default() },
+        ];
+
+        let report = adjacency_report(&layout(), &room_requirements);
+
+        let pairs = |list: &[RequestedAdjacency]| -> Vec<(String, String)> {
+            list.iter().map(|r| (r.room_id.clone(), r.other_room_id.clone())).collect()
+        };
+        assert_eq!(pairs(&report.satisfied), vec![("living".to_string(), "kitchen".to_string())]);
+        assert_eq!(pairs(&report.forbidden_present), vec![("living".to_string(), "bath".to_string())]);
+        assert_eq!(pairs(&report.missing), vec![("kitchen".to_string(), "bed".to_string())]);
+
+        let dot = report.to_dot();
+        assert!(dot.starts_with("graph adjacency {"));
+        assert!(dot.contains("\"living\" -- \"kitchen\" [label=\"3.00 m\", color=green];"));
+        assert!(dot.contains("\"kitchen\" -- \"bed\" [label=\"missing\", style=dashed, color=gray];"));
+        assert!(report.to_json().contains("\"forbidden_present\""));
+    }
+
+    // Test 3: test_avoidance_in_either_direction_wins
+    #[test]
+    fn test_avoidance_in_either_direction_wins() {
+        let room_requirements = vec![
+            RoomRequirement {
+                id: "living".to_string(),
+                min_area: 20.0,
+                relations: vec![AdjacencyRelation {
+                    room_id: "kitchen".to_string(),
+                    strength: AdjacencyStrength::Preferred,
+                    weight: 2.0,
+                }],
+                adjacent_to: vec!["bath".to_string()],
+                ..Default::default()
+            },
+            RoomRequirement {
+                id: "kitchen".to_string(),
+                min_area: 12.0,
+                relations: vec![AdjacencyRelation {
+                    room_id: "living".to_string(),
+                    strength: AdjacencyStrength::Avoid,
+                    weight: 3.0,
+                }],
+                ..Default::default()
+            },
+            RoomRequirement {
+                id: "bath".to_string(),
+                min_area: 12.0,
+                not_adjacent_to: vec!["living".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let report = adjacency_report(&layout(), &room_requirements);
+
+        let pairs = |list: &[RequestedAdjacency]| -> Vec<(String, String)> {
+            list.iter().map(|r| (r.room_id.clone(), r.other_room_id.clone())).collect()
+        };
+        assert!(report.satisfied.is_empty());
+        assert_eq!(
+            pairs(&report.forbidden_present),
+            vec![("bath".to_string(), "living".to_string()), ("kitchen".to_string(), "living".to_string())]
+        );
+        assert_eq!(report.forbidden_present[0].strength, AdjacencyStrength::Forbidden);
+    }
+
+    // Test 4: test_dot_escapes_room_ids
+    #[test]
+    fn test_dot_escapes_room_ids() {
+        let rooms = vec![room("bed \"A\"", 0.0, 0.0, 3.0, 3.0), room("c:\\bath", 3.0, 0.0, 3.0, 3.0)];
+        let room_requirements = vec![RoomRequirement {
+            id: "bed \"A\"".to_string(),
+            min_area: 9.0,
+            adjacent_to: vec!["c:\\bath".to_string()],
+            ..Default::default()
+        }];
+
+        let dot = adjacency_report(&rooms, &room_requirements).to_dot();
+
+        assert!(dot.contains(r#"    "bed \"A\"";"#));
+        assert!(dot.contains(r#"    "bed \"A\"" -- "c:\\bath" [label="3.00 m", color=green];"#));
+    }
+}
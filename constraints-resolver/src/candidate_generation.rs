@@ -1,11 +1,18 @@
-use crate::types::{Room, RoomRequirement};
+use crate::config::SolverConfig;
+use crate::types::{Alignment, Room, RoomRequirement};
+use crate::walls::WallThickness;
+
+/// Spacing of the placement grid and rounding step for room dimensions, in meters.
+pub const GRID_SIZE: f64 = 0.5;
+
+/// Height-to-width ratios tried for each room size.
+const ASPECT_RATIOS: [f64; 7] = [0.5, 0.67, 0.8, 1.0, 1.2, 1.5, 2.0];
 
 /// Generate aspect ratio candidates for a given minimum area.
 pub fn generate_aspect_ratio_candidates(min_area: f64) -> Vec<(f64, f64)> {
     let mut candidates = Vec::new();
-    let ratios = [0.5, 0.67, 0.8, 1.0, 1.2, 1.5, 2.0];
 
-    for ratio in ratios {
+    for ratio in ASPECT_RATIOS {
         let width = (min_area / ratio).sqrt();
         let height = min_area / width;
 
@@ -25,6 +32,96 @@ fn round_to_nearest_0_5(value: f64) -> f64 {
     (value * 2.0).round() / 2.0
 }
 
+/// Round a length up to the next grid step.
+pub fn round_up_to_grid(value: f64) -> f64 {
+    (value / GRID_SIZE).ceil() * GRID_SIZE
+}
+
+/// Check a room size against the requirement's area, dimension and aspect bounds.
+pub fn satisfies_size_bounds(room_req: &RoomRequirement, width: f64, height: f64) -> bool {
+    let area = width * height;
+    let aspect = width.max(height) / width.min(height);
+
+    area >= room_req.min_area
+        && room_req.max_area.is_none_or(|max_area| area <= max_area)
+        && room_req.min_width.is_none_or(|min_width| width >= min_width)
+        && room_req.min_depth.is_none_or(|min_depth| height >= min_depth)
+        && room_req.min_aspect.is_none_or(|min_aspect| aspect >= min_aspect)
+        && room_req.max_aspect.is_none_or(|max_aspect| aspect <= max_aspect)
+}
+
+/// Generate room sizes that satisfy all of the requirement's size bounds.
+///
+/// Starts from the aspect ratio candidates for `min_area`, widens any side
+/// below `min_width`/`min_depth` to the next grid step, then drops sizes that
+/// break the remaining bounds. With minimum dimensions, each aspect ratio
+/// also gives a size clamped to them before rounding, so the other side only
+/// grows as far as `min_area` needs and the size can still fit `max_area`.
+pub fn generate_size_candidates(room_req: &RoomRequirement) -> Vec<(f64, f64)> {
+    let mut sizes = Vec::new();
+    let mut push = |width: f64, height: f64| {
+        if satisfies_size_bounds(room_req, width, height) && !sizes.contains(&(width, height)) {
+            sizes.push((width, height));
+        }
+    };
+
+    for (width, height) in generate_aspect_ratio_candidates(room_req.min_area) {
+        let width = room_req.min_width.map_or(width, |min_width| width.max(round_up_to_grid(min_width)));
+        let height = room_req.min_depth.map_or(height, |min_depth| height.max(round_up_to_grid(min_depth)));
+        push(width, height);
+    }
+
+    if room_req.min_width.is_some() || room_req.min_depth.is_some() {
+        for ratio in ASPECT_RATIOS {
+            let width = (room_req.min_area / ratio).sqrt().max(room_req.min_width.unwrap_or(0.0));
+            let width = round_up_to_grid(width);
+            let height = (room_req.min_area / width).max(room_req.min_depth.unwrap_or(0.0));
+            push(width, round_up_to_grid(height));
+        }
+    }
+
+    sizes
+}
+
+/// Generate extra room sizes that copy a dimension from placed alignment partners.
+///
+/// Aspect ratio candidates rarely land on another room's exact width or
+/// depth, so each placed member of a `SameWidth`/`SameDepth` group
+/// contributes a size with that dimension and the other side stretched to
+/// reach `min_area`. Sizes already produced by `generate_size_candidates`
+/// are left out.
+pub fn generate_aligned_sizes(
+    room_req: &RoomRequirement,
+    already_placed: &[Room],
+    config: &SolverConfig,
+) -> Vec<(f64, f64)> {
+    let base_sizes = generate_size_candidates(room_req);
+    let mut sizes = Vec::new();
+
+    for (group, other) in config.alignment_partners(&room_req.id, already_placed) {
+        let (width, height) = match group.alignment {
+            Alignment::SameWidth => {
+                let height = round_up_to_grid(room_req.min_area / other.width);
+                (other.width, room_req.min_depth.map_or(height, |min_depth| height.max(round_up_to_grid(min_depth))))
+            }
+            Alignment::SameDepth => {
+                let width = round_up_to_grid(room_req.min_area / other.height);
+                (room_req.min_width.map_or(width, |min_width| width.max(round_up_to_grid(min_width))), other.height)
+            }
+            _ => continue,
+        };
+
+        if satisfies_size_bounds(room_req, width, height)
+            && !base_sizes.contains(&(width, height))
+            && !sizes.contains(&(width, height))
+        {
+            sizes.push((width, height));
+        }
+    }
+
+    sizes
+}
+
 /// Generate grid positions for a given room size and boundary size.
 pub fn generate_grid_positions(
     room_width: f64,
@@ -33,16 +130,15 @@ pub fn generate_grid_positions(
     boundary_height: f64,
 ) -> Vec<(f64, f64)> {
     let mut positions = Vec::new();
-    let grid_size = 0.5;
 
     let mut x = 0.0;
     while (x + room_width) <= boundary_width {
         let mut y = 0.0;
         while y + room_height <= boundary_height {
             positions.push((x, y));
-            y += grid_size;
+            y += GRID_SIZE;
         }
-        x += grid_size;
+        x += GRID_SIZE;
     }
 
     positions
@@ -52,11 +148,58 @@ pub fn generate_candidate_positions(
     room_req: &RoomRequirement,
     boundary_width: f64,
     boundary_height: f64,
+) -> Vec<Room> {
+    positions_for_sizes(room_req, generate_size_candidates(room_req), boundary_width, boundary_height)
+}
+
+/// Generate candidates whose net size inside the walls can meet the size bounds.
+///
+/// Each size from `generate_size_candidates` is grown by every wall
+/// allowance along each side, since the walls a room loses area to depend on
+/// whether it ends up on the boundary. Candidates are not checked against
+/// the net bounds here; the hard constraint check does that.
+pub fn generate_walled_candidate_positions(
+    room_req: &RoomRequirement,
+    walls: &WallThickness,
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Vec<Room> {
+    let allowances = walls.allowances(GRID_SIZE);
+    let mut sizes = Vec::new();
+    for (width, height) in generate_size_candidates(room_req) {
+        for extra_width in allowances.iter() {
+            for extra_height in allowances.iter() {
+                let size = (width + extra_width, height + extra_height);
+                if !sizes.contains(&size) {
+                    sizes.push(size);
+                }
+            }
+        }
+    }
+    positions_for_sizes(room_req, sizes, boundary_width, boundary_height)
+}
+
+/// Generate candidates at every grid position for the sizes from `generate_aligned_sizes`.
+pub fn generate_aligned_candidate_positions(
+    room_req: &RoomRequirement,
+    already_placed: &[Room],
+    config: &SolverConfig,
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Vec<Room> {
+    let sizes = generate_aligned_sizes(room_req, already_placed, config);
+    positions_for_sizes(room_req, sizes, boundary_width, boundary_height)
+}
+
+fn positions_for_sizes(
+    room_req: &RoomRequirement,
+    sizes: Vec<(f64, f64)>,
+    boundary_width: f64,
+    boundary_height: f64,
 ) -> Vec<Room> {
     let mut candidates = Vec::new();
-    let aspect_ratio_candidates = generate_aspect_ratio_candidates(room_req.min_area);
 
-    for (width, height) in aspect_ratio_candidates {
+    for (width, height) in sizes {
         let grid_positions =
             generate_grid_positions(width, height, boundary_width, boundary_height);
         for (x, y) in grid_positions {
@@ -74,8 +217,10 @@ pub fn generate_candidate_positions(
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests {
     use super::*;
+    use crate::types::AlignmentGroup;
 
     #[test]
     // Test 1: test_generate_aspect_ratio_candidates_returns_multiple_candidates
@@ -190,6 +335,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         let candidates = generate_candidate_positions(&room_req, 10.0, 10.0);
         assert!(candidates.len() > 0);
@@ -204,6 +350,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         let candidates = generate_candidate_positions(&room_req, 10.0, 10.0);
         
@@ -221,6 +368,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         let candidates = generate_candidate_positions(&room_req, 10.0, 10.0);
         
@@ -242,6 +390,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         let candidates = generate_candidate_positions(&room_req, 8.0, 8.0);
         
@@ -274,6 +423,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         let candidates = generate_candidate_positions(&room_req, 10.0, 10.0);
         
@@ -284,4 +434,99 @@ mod tests {
             candidates.len()
         );
     }
+
+    // Test 13: test_size_candidates_unchanged_without_bounds
+    #[test]
+    fn test_size_candidates_unchanged_without_bounds() {
+        let room_req = RoomRequirement {
+            id: "test_room".to_string(),
+            min_area: 20.0,
+            ..Default::default()
+        };
+        assert_eq!(generate_size_candidates(&room_req), generate_aspect_ratio_candidates(20.0));
+    }
+
+    // Test 14: test_size_candidates_widened_to_min_dimensions
+    #[test]
+    fn test_size_candidates_widened_to_min_dimensions() {
+        // 8 m² only yields 2x4 and 4x2; both sides must grow to reach 3 m
+        let room_req = RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 8.0,
+            min_width: Some(3.0),
+            min_depth: Some(3.0),
+            ..Default::default()
+        };
+        let sizes = generate_size_candidates(&room_req);
+
+        assert!(!sizes.is_empty());
+        for (width, height) in sizes {
+            assert!(width >= 3.0 && height >= 3.0, "Size {}x{} below minimum dimensions", width, height);
+        }
+    }
+
+    // Test 15: test_size_candidates_respect_max_area_and_aspect
+    #[test]
+    fn test_size_candidates_respect_max_area_and_aspect() {
+        let room_req = RoomRequirement {
+            id: "office".to_string(),
+            min_area: 20.0,
+            max_area: Some(20.0),
+            max_aspect: Some(1.3),
+            ..Default::default()
+        };
+        let sizes = generate_size_candidates(&room_req);
+
+        assert!(!sizes.is_empty());
+        for (width, height) in sizes {
+            assert!(width * height <= 20.0);
+            assert!(width.max(height) / width.min(height) <= 1.3);
+        }
+    }
+
+    // Test 16: test_satisfies_size_bounds_rejects_corridor_shape
+    #[test]
+    fn test_satisfies_size_bounds_rejects_corridor_shape() {
+        let room_req = RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 20.0,
+            max_aspect: Some(2.0),
+            ..Default::default()
+        };
+        assert!(!satisfies_size_bounds(&room_req, 1.0, 20.0));
+        assert!(satisfies_size_bounds(&room_req, 4.0, 5.0));
+    }
+
+    // Test 17: test_aligned_sizes_copy_partner_depth
+    #[test]
+    fn test_aligned_sizes_copy_partner_depth() {
+        let room_req = RoomRequirement { id: "bedroom2".to_string(), min_area: 9.0, ..Default::default() };
+        let placed = vec![Room { id: "bedroom1".to_string(), x: 0.0, y: 0.0, width: 3.0, height: 4.0 }];
+        let config = SolverConfig::default().with_alignment_group(AlignmentGroup {
+            room_ids: vec!["bedroom1".to_string(), "bedroom2".to_string()],
+            alignment: Alignment::SameDepth,
+            hard: true,
+            weight: 0.0,
+        });
+
+        // 9 m² at 4 m deep needs 2.25 m, rounded up to the grid
+        assert_eq!(generate_aligned_sizes(&room_req, &placed, &config), vec![(2.5, 4.0)]);
+        assert!(generate_aligned_sizes(&room_req, &placed, &SolverConfig::default()).is_empty());
+    }
+
+    // Test 18: test_size_candidates_clamped_before_max_area_check
+    #[test]
+    fn test_size_candidates_clamped_before_max_area_check() {
+        // Widening the exact 2x4 and 4x2 sizes gives 12 m², but 3x3 fits under the 10 m² cap
+        let room_req = RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 8.0,
+            min_width: Some(3.0),
+            min_depth: Some(3.0),
+            max_area: Some(10.0),
+            ..Default::default()
+        };
+
+        assert_eq!(generate_size_candidates(&room_req), vec![(3.0, 3.0)]);
+    }
 }
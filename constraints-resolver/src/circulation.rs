@@ -0,0 +1,497 @@
+use std::collections::VecDeque;
+
+use crate::candidate_generation::GRID_SIZE;
+use crate::geometry::{CellGrid, Rectangle};
+use crate::types::{AdjacencyStrength, Entrance, Room, RoomRequirement};
+
+/// Corridors allocated for a layout and the rooms they could not reach.
+#[derive(Debug, Clone)]
+pub struct CirculationPlan {
+    /// Corridor space, named `corridor_1`, `corridor_2`, ...
+    pub corridors: Vec<Room>,
+    /// Rooms with no route from an entrance, in layout order.
+    pub unreachable_room_ids: Vec<String>,
+}
+
+/// Allocate corridors in free space so every room can be reached from an entrance.
+///
+/// A room is reached when an entrance lies on its outline, or when it shares
+/// at least `corridor_width` of wall with a corridor or with a reached room
+/// that neither lists as `Avoid` or `Forbidden`, as in `unreachable_rooms`.
+/// Corridors are grown one shortest path at a time: a `corridor_width`
+/// square is moved cell by cell through free space from the reached part of
+/// the plan to the nearest unreached room, and the squares it passes through
+/// become corridor. This keeps corridor area small without guaranteeing the
+/// minimum.
+pub fn generate_corridors(
+    rooms: &[Room],
+    room_requirements: &[RoomRequirement],
+    entrances: &[Entrance],
+    corridor_width: f64,
+    boundary_width: f64,
+    boundary_height: f64,
+) -> CirculationPlan {
+    let grid = CellGrid::new(boundary_width, boundary_height, GRID_SIZE);
+    let rects: Vec<Rectangle> = rooms.iter().map(Rectangle::from_room).collect();
+    let closed = closed_connections(rooms, room_requirements);
+    let allowed = |a: usize, b: usize| !closed[a].contains(&rooms[b].id) && !closed[b].contains(&rooms[a].id);
+    let square_cells = (corridor_width / GRID_SIZE - 1e-9).ceil().max(1.0) as usize;
+
+    // Top-left cells where a corridor square fits entirely in free space
+    let free: Vec<Vec<bool>> = (0..grid.columns)
+        .map(|i| (0..grid.rows).map(|j| !grid.is_covered(i, j, &rects)).collect())
+        .collect();
+    let fits = |i: usize, j: usize| {
+        i + square_cells <= grid.columns
+            && j + square_cells <= grid.rows
+            && (i..i + square_cells).all(|ci| (j..j + square_cells).all(|cj| free[ci][cj]))
+    };
+    let square = |i: usize, j: usize| grid.span(i, j, i + square_cells, j + square_cells);
+    let opens_onto = |corridor: &Rectangle, rect: &Rectangle| corridor.shared_edge_length(rect) >= corridor_width - 1e-9;
+
+    let mut reached: Vec<bool> = rects
+        .iter()
+        .map(|rect| entrances.iter().any(|entrance| rect.contains_point(entrance.x, entrance.y)))
+        .collect();
+    let mut corridor_squares: Vec<(usize, usize)> = Vec::new();
+
+    loop {
+        spread_through_rooms(&rects, &mut reached, corridor_width, allowed);
+        if reached.iter().all(|r| *r) {
+            break;
+        }
+
+        // Multi-source BFS over square positions
+        let mut previous: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; grid.rows]; grid.columns];
+        let mut visited = vec![vec![false; grid.rows]; grid.columns];
+        let mut queue = VecDeque::new();
+        let positions = (0..grid.columns).flat_map(|i| (0..grid.rows).map(move |j| (i, j)));
+        for (i, j) in positions.filter(|&(i, j)| fits(i, j)) {
+            let position = square(i, j);
+            let is_source = corridor_squares.contains(&(i, j))
+                || entrances.iter().any(|entrance| position.contains_point(entrance.x, entrance.y))
+                || rects.iter().zip(reached.iter()).any(|(rect, reached)| *reached && opens_onto(&position, rect));
+            if is_source {
+                visited[i][j] = true;
+                queue.push_back((i, j));
+            }
+        }
+
+        let mut target = None;
+        while let Some((i, j)) = queue.pop_front() {
+            let position = square(i, j);
+            if rects.iter().zip(reached.iter()).any(|(rect, reached)| !*reached && opens_onto(&position, rect)) {
+                target = Some((i, j));
+                break;
+            }
+
+            let neighbours = [
+                (i.wrapping_sub(1), j),
+                (i + 1, j),
+                (i, j.wrapping_sub(1)),
+                (i, j + 1),
+            ];
+            for (ni, nj) in neighbours {
+                if ni < grid.columns && nj < grid.rows && !visited[ni][nj] && fits(ni, nj) {
+                    visited[ni][nj] = true;
+                    previous[ni][nj] = Some((i, j));
+                    queue.push_back((ni, nj));
+                }
+            }
+        }
+
+        let Some(mut step) = target else { break };
+        loop {
+            if !corridor_squares.contains(&step) {
+                corridor_squares.push(step);
+            }
+            let position = square(step.0, step.1);
+            for (rect, reached) in rects.iter().zip(reached.iter_mut()) {
+                *reached |= opens_onto(&position, rect);
+            }
+            match previous[step.0][step.1] {
+                Some(before) => step = before,
+                None => break,
+            }
+        }
+    }
+
+    let corridor_rects: Vec<Rectangle> = corridor_squares.iter().map(|&(i, j)| square(i, j)).collect();
+    let corridors = grid
+        .merge_cells(|i, j| grid.is_covered(i, j, &corridor_rects))
+        .into_iter()
+        .enumerate()
+        .map(|(index, rect)| Room {
+            id: format!("corridor_{}", index + 1),
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        })
+        .collect();
+
+    CirculationPlan {
+        corridors,
+        unreachable_room_ids: rooms
+            .iter()
+            .zip(reached.iter())
+            .filter(|(_, reached)| !**reached)
+            .map(|(room, _)| room.id.clone())
+            .collect(),
+    }
+}
+
+/// Rooms that cannot be reached from an entrance through allowed connections.
+///
+/// Spaces containing an entrance on their outline are reached; from there a
+/// route may pass between any two spaces sharing a wall, unless either lists
+/// the other as `Avoid` or `Forbidden`, since no door should join them.
+/// Spaces without a requirement, such as corridors, connect to everything
+/// they touch. Returns the unreached ids in layout order.
+pub fn unreachable_rooms(spaces: &[Room], room_requirements: &[RoomRequirement], entrances: &[Entrance]) -> Vec<String> {
+    let rects: Vec<Rectangle> = spaces.iter().map(Rectangle::from_room).collect();
+    let closed = closed_connections(spaces, room_requirements);
+    let allowed = |a: usize, b: usize| !closed[a].contains(&spaces[b].id) && !closed[b].contains(&spaces[a].id);
+
+    let mut reached: Vec<bool> = rects
+        .iter()
+        .map(|rect| entrances.iter().any(|entrance| rect.contains_point(entrance.x, entrance.y)))
+        .collect();
+    let mut stack: Vec<usize> = (0..rects.len()).filter(|&i| reached[i]).collect();
+    while let Some(current) = stack.pop() {
+        for next in 0..rects.len() {
+            if !reached[next] && rects[current].is_adjacent_to(&rects[next]) && allowed(current, next) {
+                reached[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    spaces
+        .iter()
+        .zip(reached.iter())
+        .filter(|(_, reached)| !**reached)
+        .map(|(space, _)| space.id.clone())
+        .collect()
+}
+
+/// Rooms of a partial layout that stay unreachable however it is completed.
+///
+/// The `unplaced` rooms, and corridors when `corridor_width` is set, can
+/// only take up free space, so a route may pass through any free grid cell.
+/// A free cell opens onto a room when it lies in a stretch of free cells
+/// along one of the room's walls at least `corridor_width` long. Without
+/// corridors any stretch will do, but only if an unplaced room may open onto
+/// the room. Between placed rooms the usual rules apply: a shared wall, at
+/// least `corridor_width` long when corridors are allocated, between rooms
+/// that do not avoid each other. Placing more rooms never reaches a
+/// room this misses, so a search can backtrack as soon as the result is
+/// non-empty.
+pub fn stranded_rooms(
+    rooms: &[Room],
+    unplaced: &[RoomRequirement],
+    room_requirements: &[RoomRequirement],
+    entrances: &[Entrance],
+    corridor_width: Option<f64>,
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Vec<String> {
+    let grid = CellGrid::new(boundary_width, boundary_height, GRID_SIZE);
+    let rects: Vec<Rectangle> = rooms.iter().map(Rectangle::from_room).collect();
+    let closed = closed_connections(rooms, room_requirements);
+    let connects = |a: usize, b: usize| {
+        let touching = match corridor_width {
+            Some(corridor_width) => rects[a].shared_edge_length(&rects[b]) >= corridor_width - 1e-9,
+            None => rects[a].is_adjacent_to(&rects[b]),
+        };
+        touching && !closed[a].contains(&rooms[b].id) && !closed[b].contains(&rooms[a].id)
+    };
+
+    // Free cells are numbered after the rooms
+    let cells: Vec<(usize, usize)> = (0..grid.columns)
+        .flat_map(|i| (0..grid.rows).map(move |j| (i, j)))
+        .filter(|&(i, j)| !grid.is_covered(i, j, &rects))
+        .collect();
+    let mut cell_index = vec![vec![None; grid.rows]; grid.columns];
+    for (index, &(i, j)) in cells.iter().enumerate() {
+        cell_index[i][j] = Some(rooms.len() + index);
+    }
+    let cell_rects: Vec<Rectangle> = cells.iter().map(|&(i, j)| grid.span(i, j, i + 1, j + 1)).collect();
+
+    let opens_onto_free_space = |room: usize| {
+        corridor_width.is_some()
+            || unplaced.iter().any(|room_req| {
+                !closed[room].contains(&room_req.id) && !avoided_rooms(room_req).contains(&rooms[room].id)
+            })
+    };
+    let mut openings: Vec<Vec<usize>> = vec![Vec::new(); rooms.len() + cells.len()];
+    for (room, rect) in rects.iter().enumerate().filter(|&(room, _)| opens_onto_free_space(room)) {
+        for cell in frontage(rect, &cell_rects, corridor_width.unwrap_or(0.0)) {
+            openings[room].push(rooms.len() + cell);
+            openings[rooms.len() + cell].push(room);
+        }
+    }
+
+    let mut reached: Vec<bool> = rects
+        .iter()
+        .chain(cell_rects.iter())
+        .map(|rect| entrances.iter().any(|entrance| rect.contains_point(entrance.x, entrance.y)))
+        .collect();
+    let mut stack: Vec<usize> = (0..reached.len()).filter(|&node| reached[node]).collect();
+    while let Some(current) = stack.pop() {
+        let mut next_nodes = openings[current].clone();
+        if current < rooms.len() {
+            next_nodes.extend((0..rooms.len()).filter(|&other| other != current && connects(current, other)));
+        } else {
+            let (i, j) = cells[current - rooms.len()];
+            let neighbours = [(i.wrapping_sub(1), j), (i + 1, j), (i, j.wrapping_sub(1)), (i, j + 1)];
+            next_nodes.extend(
+                neighbours
+                    .iter()
+                    .filter(|&&(ni, nj)| ni < grid.columns && nj < grid.rows)
+                    .filter_map(|&(ni, nj)| cell_index[ni][nj]),
+            );
+        }
+        for next in next_nodes {
+            if !reached[next] {
+                reached[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    rooms
+        .iter()
+        .zip(reached.iter())
+        .filter(|(_, reached)| !**reached)
+        .map(|(room, _)| room.id.clone())
+        .collect()
+}
+
+/// Indices of the cells along the walls of `rect` that lie in an unbroken
+/// run of cells at least `min_length` long.
+fn frontage(rect: &Rectangle, cells: &[Rectangle], min_length: f64) -> Vec<usize> {
+    // Left, right, top and bottom walls, each with the cells along it
+    let mut walls: [Vec<usize>; 4] = Default::default();
+    for (index, cell) in cells.iter().enumerate() {
+        if cell.shared_edge_length(rect) <= 1e-9 {
+            continue;
+        }
+        let wall = if (cell.x + cell.width - rect.x).abs() < 1e-9 {
+            0
+        } else if (cell.x - rect.x - rect.width).abs() < 1e-9 {
+            1
+        } else if (cell.y + cell.height - rect.y).abs() < 1e-9 {
+            2
+        } else {
+            3
+        };
+        walls[wall].push(index);
+    }
+
+    let mut open = Vec::new();
+    for (wall, along) in walls.iter_mut().enumerate() {
+        let start = |index: usize| if wall < 2 { cells[index].y } else { cells[index].x };
+        let end = |index: usize| start(index) + if wall < 2 { cells[index].height } else { cells[index].width };
+        along.sort_by(|&a, &b| start(a).total_cmp(&start(b)));
+
+        let mut run: Vec<usize> = Vec::new();
+        for &index in along.iter() {
+            if let Some(&last) = run.last()
+                && (start(index) - end(last)).abs() > 1e-9
+            {
+                close_run(&mut open, &mut run, rect, cells, min_length);
+            }
+            run.push(index);
+        }
+        close_run(&mut open, &mut run, rect, cells, min_length);
+    }
+    open
+}
+
+/// Keep the cells of a finished run if enough of it borders `rect`.
+fn close_run(open: &mut Vec<usize>, run: &mut Vec<usize>, rect: &Rectangle, cells: &[Rectangle], min_length: f64) {
+    let length: f64 = run.iter().map(|&index| cells[index].shared_edge_length(rect)).sum();
+    if length > 1e-9 && length >= min_length - 1e-9 {
+        open.append(run);
+    }
+    run.clear();
+}
+
+/// Ids each space lists as `Avoid` or `Forbidden`, in layout order.
+fn closed_connections(spaces: &[Room], room_requirements: &[RoomRequirement]) -> Vec<Vec<String>> {
+    spaces
+        .iter()
+        .map(|space| {
+            room_requirements
+                .iter()
+                .find(|r| r.id == space.id)
+                .map(avoided_rooms)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Ids a room lists as `Avoid` or `Forbidden`.
+fn avoided_rooms(room_req: &RoomRequirement) -> Vec<String> {
+    let mut avoided = room_req.related_rooms(AdjacencyStrength::Forbidden);
+    avoided.extend(room_req.related_rooms(AdjacencyStrength::Avoid));
+    avoided
+}
+
+/// Mark every room that opens onto a reached room it may connect to as reached.
+fn spread_through_rooms(
+    rects: &[Rectangle],
+    reached: &mut [bool],
+    corridor_width: f64,
+    allowed: impl Fn(usize, usize) -> bool,
+) {
+    let mut stack: Vec<usize> = (0..rects.len()).filter(|&i| reached[i]).collect();
+    while let Some(current) = stack.pop() {
+        for next in 0..rects.len() {
+            if !reached[next]
+                && rects[current].shared_edge_length(&rects[next]) >= corridor_width - 1e-9
+                && allowed(current, next)
+            {
+                reached[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    // Test 1: test_corridor_links_separated_rooms_to_entrance
+    #[test]
+    fn test_corridor_links_separated_rooms_to_entrance() {
+        // Two bedrooms along the top, entrance at the bottom edge; a 1 m corridor is needed
+        let rooms = vec![room("bed1", 0.0, 0.0, 4.0, 3.0), room("bed2", 6.0, 0.0, 4.0, 3.0)];
+        let entrances = vec![Entrance { x: 5.0, y: 8.0 }];
+
+        let plan = generate_corridors(&rooms, &[], &entrances, 1.0, 10.0, 8.0);
+
+        assert!(plan.unreachable_room_ids.is_empty());
+        assert!(!plan.corridors.is_empty());
+        let corridor_area: f64 = plan.corridors.iter().map(|c| c.width * c.height).sum();
+        assert!(corridor_area <= 10.0, "corridor area {} is not minimal", corridor_area);
+        for corridor in plan.corridors.iter() {
+            let rect = Rectangle::from_room(corridor);
+            assert!(rooms.iter().all(|r| !rect.overlaps_with(&Rectangle::from_room(r))));
+        }
+    }
+
+    // Test 2: test_rooms_reached_through_neighbours_need_no_corridor
+    #[test]
+    fn test_rooms_reached_through_neighbours_need_no_corridor() {
+        let rooms = vec![room("foyer", 0.0, 0.0, 3.0, 3.0), room("living", 3.0, 0.0, 5.0, 3.0)];
+        let entrances = vec![Entrance { x: 1.5, y: 0.0 }];
+
+        let plan = generate_corridors(&rooms, &[], &entrances, 1.0, 8.0, 6.0);
+
+        assert!(plan.corridors.is_empty());
+        assert!(plan.unreachable_room_ids.is_empty());
+    }
+
+    // Test 3: test_enclosed_room_is_unreachable
+    #[test]
+    fn test_enclosed_room_is_unreachable() {
+        // The store is walled in and shares only 0.5 m of wall with each neighbour
+        let rooms = vec![
+            room("living", 0.0, 0.0, 6.0, 4.0),
+            room("store", 6.0, 0.0, 0.5, 0.5),
+            room("kitchen", 6.5, 0.0, 1.5, 4.0),
+            room("bath", 6.0, 0.5, 0.5, 3.5),
+        ];
+        let entrances = vec![Entrance { x: 3.0, y: 0.0 }];
+
+        let plan = generate_corridors(&rooms, &[], &entrances, 1.0, 8.0, 4.0);
+
+        assert_eq!(plan.unreachable_room_ids, vec!["store".to_string()]);
+    }
+
+    // Test 4: test_unreachable_rooms_respect_forbidden_connections
+    #[test]
+    fn test_unreachable_rooms_respect_forbidden_connections() {
+        // foyer -> living -> bed, with bath only touching the bed it must not open onto
+        let rooms = vec![
+            room("foyer", 0.0, 0.0, 3.0, 3.0),
+            room("living", 3.0, 0.0, 5.0, 3.0),
+            room("bed", 3.0, 3.0, 5.0, 3.0),
+            room("bath", 8.0, 3.0, 2.0, 3.0),
+        ];
+        let room_requirements = vec![
+            RoomRequirement { id: "bath".to_string(), min_area: 4.0, not_adjacent_to: vec!["bed".to_string()], ..Default::default() },
+        ];
+        let entrances = vec![Entrance { x: 0.0, y: 1.5 }];
+
+        assert_eq!(unreachable_rooms(&rooms, &room_requirements, &entrances), vec!["bath".to_string()]);
+        assert!(unreachable_rooms(&rooms, &[], &entrances).is_empty());
+        assert_eq!(unreachable_rooms(&rooms, &[], &[]).len(), 4);
+    }
+
+    // Test 5: test_rooms_narrower_than_corridor_are_stranded
+    #[test]
+    fn test_rooms_narrower_than_corridor_are_stranded() {
+        // No wall of the store is long enough to open onto a 2 m corridor
+        let rooms = vec![room("store", 0.0, 0.0, 1.5, 1.5), room("bed", 4.0, 0.0, 3.0, 3.0)];
+        let entrances = vec![Entrance { x: 5.0, y: 8.0 }];
+
+        assert_eq!(stranded_rooms(&rooms, &[], &[], &entrances, Some(2.0), 10.0, 8.0), vec!["store".to_string()]);
+        assert!(stranded_rooms(&rooms, &[], &[], &entrances, Some(1.0), 10.0, 8.0).is_empty());
+        // An entrance on the store reaches it, but nothing opens onto the store in turn
+        let on_store = vec![Entrance { x: 0.0, y: 1.0 }];
+        assert_eq!(stranded_rooms(&rooms, &[], &[], &on_store, Some(2.0), 10.0, 8.0), vec!["bed".to_string()]);
+    }
+
+    // Test 6: test_rooms_no_unplaced_room_may_open_onto_are_stranded
+    #[test]
+    fn test_rooms_no_unplaced_room_may_open_onto_are_stranded() {
+        let rooms = vec![room("foyer", 4.0, 5.0, 2.0, 3.0), room("bath", 0.0, 0.0, 2.0, 2.0)];
+        let bed = RoomRequirement { id: "bed".to_string(), min_area: 9.0, ..Default::default() };
+        let mut avoiding_bed = bed.clone();
+        avoiding_bed.not_adjacent_to = vec!["bath".to_string()];
+        let entrances = vec![Entrance { x: 5.0, y: 8.0 }];
+
+        // Free space only stands for the bed, which could still link the bath to the foyer
+        assert!(stranded_rooms(&rooms, &[bed], &[], &entrances, None, 10.0, 8.0).is_empty());
+        assert_eq!(
+            stranded_rooms(&rooms, std::slice::from_ref(&avoiding_bed), &[], &entrances, None, 10.0, 8.0),
+            vec!["bath".to_string()]
+        );
+        assert_eq!(stranded_rooms(&rooms, &[], &[], &entrances, None, 10.0, 8.0), vec!["bath".to_string()]);
+    }
+
+    // Test 7: test_corridor_routes_skip_avoided_walls
+    #[test]
+    fn test_corridor_routes_skip_avoided_walls() {
+        // The rooms fill the boundary, and the only wall into the living room is one it avoids
+        let rooms = vec![room("foyer", 0.0, 0.0, 3.0, 3.0), room("living", 3.0, 0.0, 5.0, 3.0)];
+        let room_requirements = vec![RoomRequirement {
+            id: "living".to_string(),
+            min_area: 15.0,
+            relations: vec![crate::types::AdjacencyRelation {
+                room_id: "foyer".to_string(),
+                strength: AdjacencyStrength::Avoid,
+                weight: 1.0,
+            }],
+            ..Default::default()
+        }];
+        let entrances = vec![Entrance { x: 1.5, y: 0.0 }];
+
+        assert!(generate_corridors(&rooms, &[], &entrances, 1.0, 8.0, 3.0).unreachable_room_ids.is_empty());
+        let plan = generate_corridors(&rooms, &room_requirements, &entrances, 1.0, 8.0, 3.0);
+        assert_eq!(plan.unreachable_room_ids, vec!["living".to_string()]);
+        assert_eq!(unreachable_rooms(&rooms, &room_requirements, &entrances), vec!["living".to_string()]);
+        assert_eq!(
+            stranded_rooms(&rooms, &[], &room_requirements, &entrances, Some(1.0), 8.0, 3.0),
+            vec!["living".to_string()]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::annealing::AnnealingSettings;
+use crate::doors::DoorSettings;
+use crate::geometry::Rectangle;
+use crate::scoring::{Objective, ScoringWeights};
+use crate::types::{AlignmentGroup, Entrance, MirrorAxis, MirrorGroup, Room, ZoneContiguity};
+use crate::walls::WallThickness;
+use crate::windows::WindowSettings;
+
+/// How the solver searches for a layout of the required rooms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverStrategy {
+    /// Place rooms one at a time on the grid, backtracking from dead ends.
+    #[default]
+    Backtracking,
+    /// Build a rectangular dual of the required adjacency graph and scale it
+    /// to the boundary; suits highly connected programs.
+    RectangularDual,
+    /// Anneal over slicing trees: guillotine cuts of the boundary that never leave gaps.
+    SlicingTree,
+    /// Anneal over sequence pairs: rooms packed towards the top-left corner, never overlapping.
+    SequencePair,
+}
+
+/// Options that tune how the solver scores and searches layouts.
+///
+/// `SolverConfig::default()` reproduces the solver's built-in behaviour.
+#[derive(Default)]
+pub struct SolverConfig {
+    /// Point values for the built-in scoring terms.
+    pub weights: ScoringWeights,
+    /// Custom scoring terms added to every valid placement's score.
+    pub objectives: Vec<Box<dyn Objective>>,
+    /// Site orientation: clockwise angle in degrees from the boundary's top side to true north.
+    ///
+    /// This describes the boundary, but the boundary is only a width and a
+    /// height passed to `solve_layout`, so it travels with the other options.
+    pub north_angle: f64,
+    /// Groups of rooms that share a width, a depth or an edge line.
+    pub alignment_groups: Vec<AlignmentGroup>,
+    /// Pairs of room groups that mirror each other across a boundary axis.
+    pub mirror_groups: Vec<MirrorGroup>,
+    /// Entrance points on the boundary; zoned rooms are scored by their depth from the nearest one.
+    ///
+    /// Like `north_angle` these describe the boundary and travel with the
+    /// options; `solve_layout_with_config` checks them against its size.
+    pub entrances: Vec<Entrance>,
+    /// Zones whose rooms must (or should) form a single cluster.
+    pub zone_contiguity: Vec<ZoneContiguity>,
+    /// Grow rooms after solving to cover the boundary, leaving the rest as circulation.
+    pub fill: bool,
+    /// Minimum corridor width; when set, every room must be reachable from an entrance.
+    pub corridor_width: Option<f64>,
+    /// Reject layouts where a room cannot be reached from an entrance through
+    /// the rooms it shares walls with (corridors take over when `corridor_width` is set).
+    pub require_reachability: bool,
+    /// Door sizes for the doors placed on required connections once a layout is found.
+    pub doors: DoorSettings,
+    /// Window sizes for glazing rooms with a `glazing_ratio` once a layout is found.
+    pub windows: WindowSettings,
+    /// Wall thicknesses; `None` treats rooms as zero-thickness rectangles.
+    pub walls: Option<WallThickness>,
+    /// Search used for the required rooms; optional rooms are always added by backtracking.
+    pub strategy: SolverStrategy,
+    /// Search settings for the annealing strategies.
+    pub annealing: AnnealingSettings,
+}
+
+impl SolverConfig {
+    /// Register a custom scoring term.
+    pub fn with_objective(mut self, objective: impl Objective + 'static) -> Self {
+        self.objectives.push(Box::new(objective));
+        self
+    }
+
+    /// Require a group of rooms to share a dimension or edge line.
+    pub fn with_alignment_group(mut self, group: AlignmentGroup) -> Self {
+        self.alignment_groups.push(group);
+        self
+    }
+
+    /// Declare two groups of rooms as mirror images across a boundary axis.
+    pub fn with_mirror_group(mut self, group: MirrorGroup) -> Self {
+        self.mirror_groups.push(group);
+        self
+    }
+
+    /// The net room inside its walls, or the room itself when walls are not modelled.
+    pub fn net_room(&self, room: &Room, boundary_width: f64, boundary_height: f64) -> Room {
+        let Some(walls) = &self.walls else { return room.clone() };
+        let net = walls.net_rectangle(&Rectangle::from_room(room), boundary_width, boundary_height);
+        Room { id: room.id.clone(), x: net.x, y: net.y, width: net.width, height: net.height }
+    }
+
+    /// The room derived by mirroring this one, and the axis it is mirrored across.
+    pub fn mirror_counterpart(&self, room_id: &str) -> Option<(&str, MirrorAxis)> {
+        self.mirror_groups
+            .iter()
+            .find_map(|group| group.counterpart_of(room_id).map(|counterpart| (counterpart, group.axis)))
+    }
+
+    /// The source room this room is derived from, if it is a mirrored counterpart.
+    pub fn mirror_source(&self, room_id: &str) -> Option<&str> {
+        self.mirror_groups.iter().find_map(|group| group.source_of(room_id))
+    }
+
+    /// Alignment groups the room belongs to, paired with the members already placed.
+    pub fn alignment_partners<'a>(
+        &'a self,
+        room_id: &'a str,
+        already_placed: &'a [Room],
+    ) -> impl Iterator<Item = (&'a AlignmentGroup, &'a Room)> + 'a {
+        self.alignment_groups
+            .iter()
+            .filter(move |group| group.contains(room_id))
+            .flat_map(move |group| {
+                already_placed
+                    .iter()
+                    .filter(move |placed| placed.id != room_id && group.contains(&placed.id))
+                    .map(move |placed| (group, placed))
+            })
+    }
+}
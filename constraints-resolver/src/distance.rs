@@ -0,0 +1,154 @@
+use crate::geometry::Rectangle;
+use crate::types::{DistanceConstraint, DistanceMetric, Room};
+
+/// Measure the distance between two placed rooms.
+///
+/// `placed_rooms` is the rest of the layout, used by `WalkingPath` to route
+/// through intermediate rooms.
+pub fn measure_distance(metric: DistanceMetric, room: &Room, other: &Room, placed_rooms: &[Room]) -> f64 {
+    let room_rect = Rectangle::from_room(room);
+    let other_rect = Rectangle::from_room(other);
+
+    match metric {
+        DistanceMetric::CenterToCenter => room_rect.center_distance(&other_rect),
+        DistanceMetric::EdgeToEdge => room_rect.edge_distance(&other_rect),
+        DistanceMetric::WalkingPath => walking_distance(room, other, placed_rooms),
+    }
+}
+
+/// Length of the shortest walk from one room centre to another.
+///
+/// The walk moves orthogonally between room centres and the midpoints of
+/// shared walls, so it only passes through rooms that touch. When no such
+/// route exists among the rooms placed so far, the orthogonal distance
+/// between the centres is returned as a lower bound.
+pub fn walking_distance(room: &Room, other: &Room, placed_rooms: &[Room]) -> f64 {
+    walking_route(room, other, placed_rooms)
+        .unwrap_or_else(|| manhattan(Rectangle::from_room(room).center(), Rectangle::from_room(other).center()))
+}
+
+/// Whether a hard distance constraint is broken for good by the rooms placed so far.
+///
+/// Placing more rooms never moves either room, but it can open a shorter
+/// walk. A walk is never shorter than the orthogonal distance between the
+/// centres, and one already below `min_distance` stays too short; any other
+/// walking distance is only settled on the complete layout.
+pub fn is_ruled_out(constraint: &DistanceConstraint, room: &Room, other: &Room, placed_rooms: &[Room]) -> bool {
+    if constraint.metric != DistanceMetric::WalkingPath {
+        return !constraint.is_satisfied_by(measure_distance(constraint.metric, room, other, placed_rooms));
+    }
+
+    let lower_bound = manhattan(Rectangle::from_room(room).center(), Rectangle::from_room(other).center());
+    constraint.max_distance.is_some_and(|max_distance| lower_bound > max_distance)
+        || walking_route(room, other, placed_rooms)
+            .is_some_and(|distance| constraint.min_distance.is_some_and(|min_distance| distance < min_distance))
+}
+
+/// Length of the shortest walk between two rooms through the placed rooms,
+/// or `None` when they are not connected.
+pub fn walking_route(room: &Room, other: &Room, placed_rooms: &[Room]) -> Option<f64> {
+    let mut rects = vec![Rectangle::from_room(room), Rectangle::from_room(other)];
+    rects.extend(
+        placed_rooms
+            .iter()
+            .filter(|r| r.id != room.id && r.id != other.id)
+            .map(Rectangle::from_room),
+    );
+
+    // Dijkstra from rects[0] to rects[1]; layouts are small so a linear scan is enough
+    let mut distances = vec![f64::INFINITY; rects.len()];
+    let mut visited = vec![false; rects.len()];
+    distances[0] = 0.0;
+
+    loop {
+        let current = (0..rects.len())
+            .filter(|&i| !visited[i] && distances[i].is_finite())
+            .min_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+        let Some(current) = current else { break };
+        if current == 1 {
+            return Some(distances[1]);
+        }
+        visited[current] = true;
+
+        for next in 0..rects.len() {
+            if visited[next] {
+                continue;
+            }
+            if let Some(wall) = rects[current].shared_edge(&rects[next]) {
+                let step = manhattan(rects[current].center(), wall.midpoint())
+                    + manhattan(wall.midpoint(), rects[next].center());
+                distances[next] = distances[next].min(distances[current] + step);
+            }
+        }
+    }
+
+    None
+}
+
+fn manhattan(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.0 - from.0).abs() + (to.1 - from.1).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    // Test 1: test_center_to_center_distance
+    #[test]
+    fn test_center_to_center_distance() {
+        let a = room("a", 0.0, 0.0, 2.0, 2.0);
+        let b = room("b", 4.0, 0.0, 2.0, 2.0);
+        assert_eq!(measure_distance(DistanceMetric::CenterToCenter, &a, &b, &[]), 4.0);
+    }
+
+    // Test 2: test_walking_path_through_intermediate_room
+    #[test]
+    fn test_walking_path_through_intermediate_room() {
+        // a and c are apart, hall connects them along the top
+        let a = room("a", 0.0, 2.0, 2.0, 2.0);
+        let hall = room("hall", 0.0, 0.0, 6.0, 2.0);
+        let c = room("c", 4.0, 2.0, 2.0, 2.0);
+
+        // a centre (1,3) -> wall (1,2) -> hall centre (3,1) -> wall (5,2) -> c centre (5,3)
+        let distance = walking_distance(&a, &c, &[hall]);
+        assert_eq!(distance, 1.0 + 3.0 + 3.0 + 1.0);
+    }
+
+    // Test 3: test_walking_path_falls_back_to_orthogonal_distance
+    #[test]
+    fn test_walking_path_falls_back_to_orthogonal_distance() {
+        let a = room("a", 0.0, 0.0, 2.0, 2.0);
+        let b = room("b", 5.0, 5.0, 2.0, 2.0);
+        assert_eq!(walking_distance(&a, &b, &[]), 10.0);
+    }
+
+    // Test 4: test_walking_constraints_ruled_out_only_for_good
+    #[test]
+    fn test_walking_constraints_ruled_out_only_for_good() {
+        let a = room("a", 0.0, 2.0, 2.0, 2.0);
+        let c = room("c", 4.0, 2.0, 2.0, 2.0);
+        let hall = room("hall", 0.0, 0.0, 6.0, 2.0);
+        let walking = |min_distance: Option<f64>, max_distance: Option<f64>| DistanceConstraint {
+            room_id: "c".to_string(),
+            min_distance,
+            max_distance,
+            metric: DistanceMetric::WalkingPath,
+            hard: true,
+            weight: 0.0,
+        };
+
+        // No route yet: only the 4 m orthogonal lower bound is known
+        assert!(is_ruled_out(&walking(None, Some(3.0)), &a, &c, &[]));
+        assert!(!is_ruled_out(&walking(None, Some(5.0)), &a, &c, &[]));
+        assert!(!is_ruled_out(&walking(Some(10.0), None), &a, &c, &[]));
+
+        // The 8 m route through the hall may still be shortened, but never lengthened
+        assert!(is_ruled_out(&walking(Some(10.0), None), &a, &c, std::slice::from_ref(&hall)));
+        assert!(!is_ruled_out(&walking(None, Some(5.0)), &a, &c, std::slice::from_ref(&hall)));
+        assert_eq!(walking_route(&a, &c, &[]), None);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::{Rectangle, Segment};
+use crate::types::{AdjacencyStrength, Room, RoomRequirement};
+
+/// Door sizes used when placing doors on shared walls.
+///
+/// Missing fields deserialize to their defaults, so JavaScript callers can
+/// override a single value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DoorSettings {
+    /// Door width used when the wall is long enough.
+    pub width: f64,
+    /// Narrowest door accepted on a short wall.
+    pub min_width: f64,
+    /// Wall kept clear between a door and either end of the shared wall.
+    pub corner_clearance: f64,
+}
+
+impl Default for DoorSettings {
+    fn default() -> Self {
+        Self {
+            width: 0.9,
+            min_width: 0.7,
+            corner_clearance: 0.1,
+        }
+    }
+}
+
+/// A door in the wall between two rooms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Door {
+    pub room_id: String,
+    pub other_room_id: String,
+    /// Door opening, lying on the shared wall.
+    pub segment: Segment,
+}
+
+impl Door {
+    /// Width of the door opening.
+    pub fn width(&self) -> f64 {
+        self.segment.length()
+    }
+}
+
+/// Doors placed for a layout and the connections that had no room for one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoorPlan {
+    pub doors: Vec<Door>,
+    /// Required connections whose shared wall is too short for `min_width`
+    /// plus the corner clearance on both ends.
+    pub blocked_connections: Vec<(String, String)>,
+}
+
+/// Place one door on the shared wall of every required connection.
+///
+/// A required connection is a `Required` adjacency between two placed rooms,
+/// counted once per pair. The door is centred on the wall segment shared by
+/// the two rooms and is as wide as `settings.width` allows once
+/// `settings.corner_clearance` is kept free at both ends.
+pub fn place_doors(rooms: &[Room], room_requirements: &[RoomRequirement], settings: &DoorSettings) -> DoorPlan {
+    let mut doors = Vec::new();
+    let mut blocked_connections = Vec::new();
+
+    for (room_id, other_room_id) in required_connections(rooms, room_requirements) {
+        let room = rooms.iter().find(|r| r.id == room_id);
+        let other = rooms.iter().find(|r| r.id == other_room_id);
+        let (Some(room), Some(other)) = (room, other) else { continue };
+
+        let shared = Rectangle::from_room(room).shared_edge(&Rectangle::from_room(other));
+        match shared.and_then(|wall| door_opening(&wall, settings)) {
+            Some(segment) => doors.push(Door { room_id, other_room_id, segment }),
+            None => blocked_connections.push((room_id, other_room_id)),
+        }
+    }
+
+    DoorPlan { doors, blocked_connections }
+}
+
+/// Required adjacencies between placed rooms, each pair once in layout order.
+fn required_connections(rooms: &[Room], room_requirements: &[RoomRequirement]) -> Vec<(String, String)> {
+    let is_placed = |room_id: &str| rooms.iter().any(|r| r.id == room_id);
+
+    let mut connections: Vec<(String, String)> = Vec::new();
+    for room in rooms {
+        let Some(room_req) = room_requirements.iter().find(|r| r.id == room.id) else { continue };
+        for other_id in room_req.related_rooms(AdjacencyStrength::Required) {
+            let known = connections
+                .iter()
+                .any(|(a, b)| (*a == room.id && *b == other_id) || (*a == other_id && *b == room.id));
+            if is_placed(&other_id) && !known {
+                connections.push((room.id.clone(), other_id));
+            }
+        }
+    }
+    connections
+}
+
+/// The door opening centred on a wall, or `None` when the wall is too short.
+fn door_opening(wall: &Segment, settings: &DoorSettings) -> Option<Segment> {
+    let length = wall.length();
+    let width = settings.width.min(length - 2.0 * settings.corner_clearance);
+    if width < settings.min_width - 1e-9 || width <= 0.0 {
+        return None;
+    }
+
+    let (mid_x, mid_y) = wall.midpoint();
+    let (unit_x, unit_y) = ((wall.x2 - wall.x1) / length, (wall.y2 - wall.y1) / length);
+    let half = width / 2.0;
+    Some(Segment {
+        x1: mid_x - unit_x * half,
+        y1: mid_y - unit_y * half,
+        x2: mid_x + unit_x * half,
+        y2: mid_y + unit_y * half,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    fn requirement(id: &str, adjacent_to: &[&str]) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area: 1.0,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    // Test 1: test_door_centred_on_shared_wall
+    #[test]
+    fn test_door_centred_on_shared_wall() {
+        // The rooms share the wall x = 4 from y = 1 to y = 3
+        let rooms = vec![room("living", 0.0, 0.0, 4.0, 3.0), room("kitchen", 4.0, 1.0, 3.0, 4.0)];
+        let room_requirements = vec![requirement("living", &["kitchen"]), requirement("kitchen", &["living"])];
+
+        let plan = place_doors(&rooms, &room_requirements, &DoorSettings::default());
+
+        assert!(plan.blocked_connections.is_empty());
+        assert_eq!(plan.doors.len(), 1);
+        let door = &plan.doors[0];
+        assert_eq!((door.room_id.as_str(), door.other_room_id.as_str()), ("living", "kitchen"));
+        assert_eq!((door.segment.x1, door.segment.x2), (4.0, 4.0));
+        assert!((door.segment.y1 - 1.55).abs() < 1e-9 && (door.segment.y2 - 2.45).abs() < 1e-9);
+    }
+
+    // Test 2: test_short_wall_narrows_or_blocks_door
+    #[test]
+    fn test_short_wall_narrows_or_blocks_door() {
+        let settings = DoorSettings::default();
+
+        // 0.9 m of shared wall leaves 0.7 m once both corners are cleared
+        let rooms = vec![room("hall", 0.0, 0.0, 2.0, 2.0), room("wc", 2.0, 1.1, 1.0, 2.0)];
+        let plan = place_doors(&rooms, &[requirement("hall", &["wc"])], &settings);
+        assert!((plan.doors[0].width() - 0.7).abs() < 1e-9);
+
+        let rooms = vec![room("hall", 0.0, 0.0, 2.0, 2.0), room("wc", 2.0, 1.5, 1.0, 2.0)];
+        let plan = place_doors(&rooms, &[requirement("hall", &["wc"])], &settings);
+        assert!(plan.doors.is_empty());
+        assert_eq!(plan.blocked_connections, vec![("hall".to_string(), "wc".to_string())]);
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::candidate_generation::{generate_size_candidates, GRID_SIZE};
+use crate::types::{AdjacencyStrength, RoomRequirement};
+
+/// A necessary condition for a solution that the requirements fail.
+///
+/// Each variant is a cheap bound checked before search; failing one proves
+/// no layout exists, so the solver can report it without backtracking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Infeasibility {
+    /// The rooms need more floor area than the boundary provides.
+    TotalAreaExceedsBoundary { required_area: f64, boundary_area: f64 },
+    /// None of the room's candidate sizes fit inside the boundary or meet its size bounds.
+    RoomDoesNotFit { room_id: String, min_area: f64 },
+    /// The exterior-wall rooms need more perimeter than the boundary has.
+    ExteriorWallCapacityExceeded { required_length: f64, perimeter: f64 },
+    /// A room must touch more neighbours than its perimeter can hold.
+    TooManyAdjacencies {
+        room_id: String,
+        required: usize,
+        max_possible: usize,
+    },
+}
+
+impl fmt::Display for Infeasibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Infeasibility::TotalAreaExceedsBoundary { required_area, boundary_area } => write!(
+                f,
+                "Rooms need {:.2} m² but the boundary only has {:.2} m²",
+                required_area, boundary_area
+            ),
+            Infeasibility::RoomDoesNotFit { room_id, min_area } => write!(
+                f,
+                "Room {} ({:.2} m²) has no candidate size that fits the boundary",
+                room_id, min_area
+            ),
+            Infeasibility::ExteriorWallCapacityExceeded { required_length, perimeter } => write!(
+                f,
+                "Exterior-wall rooms need at least {:.2} m of facade but the perimeter is {:.2} m",
+                required_length, perimeter
+            ),
+            Infeasibility::TooManyAdjacencies { room_id, required, max_possible } => write!(
+                f,
+                "Room {} must be adjacent to {} rooms but its perimeter allows at most {}",
+                room_id, required, max_possible
+            ),
+        }
+    }
+}
+
+/// Run cheap necessary-condition checks on the requirements.
+///
+/// Returns the first bound that fails. Passing does not guarantee a
+/// solution exists, only that none of these obvious obstacles apply.
+pub fn check_feasibility(
+    room_requirements: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Result<(), Infeasibility> {
+    let boundary_area = boundary_width * boundary_height;
+    let required_area: f64 = room_requirements.iter().map(|r| r.min_area).sum();
+    if required_area > boundary_area {
+        return Err(Infeasibility::TotalAreaExceedsBoundary { required_area, boundary_area });
+    }
+
+    let mut exterior_length = 0.0;
+    for room_req in room_requirements {
+        let fitting = fitting_sizes(room_req, boundary_width, boundary_height);
+        if fitting.is_empty() {
+            return Err(Infeasibility::RoomDoesNotFit {
+                room_id: room_req.id.clone(),
+                min_area: room_req.min_area,
+            });
+        }
+
+        // A room on the facade occupies at least its shorter side (or its minimum facade) of the perimeter
+        if room_req.needs_exterior_wall() {
+            let shortest_side = fitting
+                .iter()
+                .map(|(width, height)| width.min(*height))
+                .fold(f64::INFINITY, f64::min);
+            exterior_length += shortest_side.max(room_req.min_facade_length.unwrap_or(0.0));
+        }
+
+        // Every neighbour needs at least one grid step of shared wall
+        let max_perimeter = fitting
+            .iter()
+            .map(|(width, height)| 2.0 * (width + height))
+            .fold(0.0, f64::max);
+        let max_possible = (max_perimeter / GRID_SIZE).floor() as usize;
+        let required = required_neighbours(room_req, room_requirements);
+        if required > max_possible {
+            return Err(Infeasibility::TooManyAdjacencies {
+                room_id: room_req.id.clone(),
+                required,
+                max_possible,
+            });
+        }
+    }
+
+    let perimeter = 2.0 * (boundary_width + boundary_height);
+    if exterior_length > perimeter {
+        return Err(Infeasibility::ExteriorWallCapacityExceeded { required_length: exterior_length, perimeter });
+    }
+
+    Ok(())
+}
+
+fn fitting_sizes(room_req: &RoomRequirement, boundary_width: f64, boundary_height: f64) -> Vec<(f64, f64)> {
+    generate_size_candidates(room_req)
+        .into_iter()
+        .filter(|(width, height)| *width <= boundary_width && *height <= boundary_height)
+        .collect()
+}
+
+/// Distinct rooms this room must share a wall with, from either side of the relation.
+fn required_neighbours(room_req: &RoomRequirement, room_requirements: &[RoomRequirement]) -> usize {
+    let mut neighbours: HashSet<String> = room_req.related_rooms(AdjacencyStrength::Required).into_iter().collect();
+    for other in room_requirements {
+        if other.related_rooms(AdjacencyStrength::Required).contains(&room_req.id) {
+            neighbours.insert(other.id.clone());
+        }
+    }
+    neighbours.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, min_area: f64, adjacent_to: &[&str], has_exterior_wall: bool) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            not_adjacent_to: vec![],
+            has_exterior_wall,
+            ..Default::default()
+        }
+    }
+
+    // Test 1: test_feasible_program_passes
+    #[test]
+    fn test_feasible_program_passes() {
+        let rooms = vec![room("living", 20.0, &["kitchen"], true), room("kitchen", 12.0, &[], false)];
+        assert_eq!(check_feasibility(&rooms, 10.0, 10.0), Ok(()));
+    }
+
+    // Test 2: test_rejects_total_area_over_boundary
+    #[test]
+    fn test_rejects_total_area_over_boundary() {
+        let rooms = vec![room("room1", 60.0, &[], false), room("room2", 60.0, &[], false)];
+        assert_eq!(
+            check_feasibility(&rooms, 10.0, 10.0),
+            Err(Infeasibility::TotalAreaExceedsBoundary { required_area: 120.0, boundary_area: 100.0 })
+        );
+    }
+
+    // Test 3: test_rejects_room_too_large_for_narrow_boundary
+    #[test]
+    fn test_rejects_room_too_large_for_narrow_boundary() {
+        // 20 m² fits by area but every candidate is wider than 2 m
+        let rooms = vec![room("living", 20.0, &[], false)];
+        assert!(matches!(
+            check_feasibility(&rooms, 2.0, 30.0),
+            Err(Infeasibility::RoomDoesNotFit { .. })
+        ));
+    }
+
+    // Test 4: test_rejects_exterior_rooms_over_perimeter
+    #[test]
+    fn test_rejects_exterior_rooms_over_perimeter() {
+        // Each 1 m² room needs at least 1 m of a 40 m perimeter; 41 need 41 m
+        let rooms: Vec<RoomRequirement> = (0..41)
+            .map(|i| room(&format!("room{}", i), 1.0, &[], true))
+            .collect();
+        assert!(matches!(
+            check_feasibility(&rooms, 10.0, 10.0),
+            Err(Infeasibility::ExteriorWallCapacityExceeded { .. })
+        ));
+    }
+
+    // Test 5: test_rejects_room_with_too_many_neighbours
+    #[test]
+    fn test_rejects_room_with_too_many_neighbours() {
+        // A 1 m² room has at most 4 m of perimeter, room for 8 neighbours
+        let neighbour_ids: Vec<String> = (0..9).map(|i| format!("n{}", i)).collect();
+        let neighbour_refs: Vec<&str> = neighbour_ids.iter().map(|s| s.as_str()).collect();
+        let mut rooms = vec![room("hub", 1.0, &neighbour_refs, false)];
+        rooms.extend(neighbour_ids.iter().map(|id| room(id, 1.0, &[], false)));
+
+        assert_eq!(
+            check_feasibility(&rooms, 20.0, 20.0),
+            Err(Infeasibility::TooManyAdjacencies {
+                room_id: "hub".to_string(),
+                required: 9,
+                max_possible: 8,
+            })
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::candidate_generation::{satisfies_size_bounds, GRID_SIZE};
+use crate::config::SolverConfig;
+use crate::geometry::{free_space_partition, Rectangle};
+use crate::scoring::score_position;
+use crate::types::{BoundarySide, Room, RoomRequirement};
+
+/// Grow rooms to cover the boundary and return what is left as circulation.
+///
+/// Rooms take turns growing one grid step on each side until none can
+/// grow further. A step is kept only if the room stays within its size
+/// bounds (`max_area`, minimum dimensions and aspect ratio) measured inside
+/// its walls, its own hard constraints still hold against every other room,
+/// and no other room's hard constraints break against it. Rooms in mirror
+/// groups keep their size so both halves stay symmetric, and no room grows
+/// into `obstacles` such as corridors.
+///
+/// Leftover space is split into rectangles named `circulation_1`,
+/// `circulation_2`, ...
+pub fn fill_layout(
+    rooms: &mut [Room],
+    room_requirements: &[RoomRequirement],
+    obstacles: &[Room],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> Vec<Room> {
+    let obstacle_rects: Vec<Rectangle> = obstacles.iter().map(Rectangle::from_room).collect();
+    let mut grew = true;
+    while grew {
+        grew = false;
+        for index in 0..rooms.len() {
+            let Some(room_req) = room_requirements.iter().find(|r| r.id == rooms[index].id) else {
+                continue;
+            };
+            if config.mirror_source(&room_req.id).is_some() || config.mirror_counterpart(&room_req.id).is_some() {
+                continue;
+            }
+
+            for side in BoundarySide::ALL {
+                let grown = grow(&rooms[index], side);
+                let grown_rect = Rectangle::from_room(&grown);
+                if obstacle_rects.iter().any(|obstacle| grown_rect.overlaps_with(obstacle)) {
+                    continue;
+                }
+                if can_grow(&grown, index, rooms, room_requirements, boundary_width, boundary_height, config) {
+                    rooms[index] = grown;
+                    grew = true;
+                }
+            }
+        }
+    }
+
+    let occupied: Vec<Rectangle> = rooms.iter().map(Rectangle::from_room).chain(obstacle_rects).collect();
+    free_space_partition(&occupied, boundary_width, boundary_height, GRID_SIZE)
+        .into_iter()
+        .enumerate()
+        .map(|(i, rect)| Room {
+            id: format!("circulation_{}", i + 1),
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        })
+        .collect()
+}
+
+/// Share of the boundary covered by rooms (net area over gross area).
+pub fn net_to_gross(rooms: &[Room], boundary_width: f64, boundary_height: f64) -> f64 {
+    let net_area: f64 = rooms.iter().map(|room| room.width * room.height).sum();
+    net_area / (boundary_width * boundary_height)
+}
+
+/// The room extended by one grid step past the given side.
+fn grow(room: &Room, side: BoundarySide) -> Room {
+    let mut grown = room.clone();
+    match side {
+        BoundarySide::Top => {
+            grown.y -= GRID_SIZE;
+            grown.height += GRID_SIZE;
+        }
+        BoundarySide::Bottom => grown.height += GRID_SIZE,
+        BoundarySide::Left => {
+            grown.x -= GRID_SIZE;
+            grown.width += GRID_SIZE;
+        }
+        BoundarySide::Right => grown.width += GRID_SIZE,
+    }
+    grown
+}
+
+fn can_grow(
+    grown: &Room,
+    index: usize,
+    rooms: &[Room],
+    room_requirements: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> bool {
+    let Some(room_req) = room_requirements.iter().find(|r| r.id == grown.id) else { return false };
+    let net = config.net_room(grown, boundary_width, boundary_height);
+    if !satisfies_size_bounds(room_req, net.width, net.height) {
+        return false;
+    }
+
+    let others: Vec<Room> = rooms
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .map(|(_, room)| room.clone())
+        .collect();
+    if score_position(grown, room_req, &others, boundary_width, boundary_height, config).has_violations {
+        return false;
+    }
+
+    // Other rooms' constraints towards the grown room
+    let grown_only = std::slice::from_ref(grown);
+    others.iter().all(|other| {
+        room_requirements.iter().find(|r| r.id == other.id).is_none_or(|other_req| {
+            !score_position(other, other_req, grown_only, boundary_width, boundary_height, config).has_violations
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test 1: test_fill_grows_rooms_to_cover_boundary
+    #[test]
+    fn test_fill_grows_rooms_to_cover_boundary() {
+        let room_requirements = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 12.0, ..Default::default() },
+            RoomRequirement { id: "kitchen".to_string(), min_area: 6.0, ..Default::default() },
+        ];
+        let mut rooms = vec![
+            Room { id: "living".to_string(), x: 0.0, y: 0.0, width: 4.0, height: 3.0 },
+            Room { id: "kitchen".to_string(), x: 4.0, y: 0.0, width: 2.0, height: 3.0 },
+        ];
+
+        let circulation = fill_layout(&mut rooms, &room_requirements, &[], 8.0, 6.0, &SolverConfig::default());
+
+        assert!(circulation.is_empty());
+        assert_eq!(net_to_gross(&rooms, 8.0, 6.0), 1.0);
+    }
+
+    // Test 2: test_fill_respects_max_area_and_labels_circulation
+    #[test]
+    fn test_fill_respects_max_area_and_labels_circulation() {
+        let room_requirements = vec![RoomRequirement {
+            id: "bath".to_string(),
+            min_area: 4.0,
+            max_area: Some(6.0),
+            ..Default::default()
+        }];
+        let mut rooms = vec![Room { id: "bath".to_string(), x: 0.0, y: 0.0, width: 2.0, height: 2.0 }];
+
+        let circulation = fill_layout(&mut rooms, &room_requirements, &[], 4.0, 4.0, &SolverConfig::default());
+
+        assert!(rooms[0].width * rooms[0].height <= 6.0);
+        let free_area: f64 = circulation.iter().map(|r| r.width * r.height).sum();
+        assert_eq!(free_area, 16.0 - rooms[0].width * rooms[0].height);
+        assert!(circulation.iter().all(|r| r.id.starts_with("circulation_")));
+    }
+}
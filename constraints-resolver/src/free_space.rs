@@ -0,0 +1,89 @@
+use crate::geometry::{is_connected, maximal_free_rectangles, Rectangle};
+use crate::types::Room;
+
+/// A maximal free rectangle and the rooms it borders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeRectangle {
+    pub rect: Rectangle,
+    /// Ids of rooms sharing an edge with the rectangle.
+    pub touching_room_ids: Vec<String>,
+}
+
+/// The part of the boundary no room covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeSpace {
+    /// Maximal free rectangles; they may overlap but together cover all free space.
+    pub rectangles: Vec<FreeRectangle>,
+    pub total_area: f64,
+    pub largest: Option<Rectangle>,
+    /// Whether all free space forms one region (true when there is none).
+    pub is_connected: bool,
+}
+
+/// Analyse the space left unused by the placed rooms.
+pub fn analyze_free_space(rooms: &[Room], boundary_width: f64, boundary_height: f64) -> FreeSpace {
+    let occupied: Vec<Rectangle> = rooms.iter().map(Rectangle::from_room).collect();
+    let maximal = maximal_free_rectangles(&occupied, boundary_width, boundary_height);
+
+    let boundary = Rectangle { x: 0.0, y: 0.0, width: boundary_width, height: boundary_height };
+    let occupied_area: f64 = occupied.iter().map(|rect| rect.overlap_area(&boundary)).sum();
+    let largest = maximal
+        .iter()
+        .copied()
+        .max_by(|a, b| (a.width * a.height).total_cmp(&(b.width * b.height)));
+
+    let rectangles = maximal
+        .iter()
+        .map(|rect| FreeRectangle {
+            rect: *rect,
+            touching_room_ids: rooms
+                .iter()
+                .filter(|room| rect.is_adjacent_to(&Rectangle::from_room(room)))
+                .map(|room| room.id.clone())
+                .collect(),
+        })
+        .collect();
+
+    FreeSpace {
+        rectangles,
+        total_area: (boundary_width * boundary_height - occupied_area).max(0.0),
+        largest,
+        is_connected: is_connected(&maximal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    // Test 1: test_free_space_between_rooms_is_split
+    #[test]
+    fn test_free_space_between_rooms_is_split() {
+        // A full-height room in the middle cuts the free space in two
+        let rooms = vec![room("living", 0.0, 0.0, 3.0, 4.0), room("hall", 4.0, 0.0, 2.0, 6.0)];
+        let free_space = analyze_free_space(&rooms, 10.0, 6.0);
+
+        assert_eq!(free_space.total_area, 60.0 - 12.0 - 12.0);
+        assert!(!free_space.is_connected);
+        assert_eq!(free_space.largest, Some(Rectangle { x: 6.0, y: 0.0, width: 4.0, height: 6.0 }));
+
+        let right = free_space.rectangles.iter().find(|r| r.rect.x == 6.0).unwrap();
+        assert_eq!(right.touching_room_ids, vec!["hall".to_string()]);
+    }
+
+    // Test 2: test_no_free_space_when_covered
+    #[test]
+    fn test_no_free_space_when_covered() {
+        let rooms = vec![room("a", 0.0, 0.0, 4.0, 4.0), room("b", 4.0, 0.0, 4.0, 4.0)];
+        let free_space = analyze_free_space(&rooms, 8.0, 4.0);
+
+        assert!(free_space.rectangles.is_empty());
+        assert_eq!(free_space.total_area, 0.0);
+        assert_eq!(free_space.largest, None);
+        assert!(free_space.is_connected);
+    }
+}
@@ -1,8 +1,29 @@
-use crate::types::Room;
+use crate::types::{Alignment, BoundarySide, MirrorAxis, RelativePosition, Room};
 
 
+/// A straight line segment between two points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl Segment {
+    /// Length of the segment.
+    pub fn length(&self) -> f64 {
+        (self.x2 - self.x1).hypot(self.y2 - self.y1)
+    }
+
+    /// Midpoint of the segment.
+    pub fn midpoint(&self) -> (f64, f64) {
+        ((self.x1 + self.x2) / 2.0, (self.y1 + self.y2) / 2.0)
+    }
+}
+
 /// A rectangle with a position and size.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rectangle {
     pub x: f64,
     pub y: f64,
@@ -28,6 +49,7 @@ impl Rectangle {
     /// - One is completely to the right of the other, OR
     /// - One is completely above the other, OR
     /// - One is completely below the other
+    ///
     /// Returns true if rectangles overlap (share area, not just touch)
     pub fn overlaps_with(&self, other: &Rectangle) -> bool {
         let x_separated = self.x + self.width <= other.x || other.x + other.width <= self.x;
@@ -54,6 +76,59 @@ impl Rectangle {
         (vertical_edge_touching && vertical_range_overlap) || (horizontal_edge_touching && horizontal_range_overlap)
     }
 
+    /// Area shared by two rectangles (0.0 when they only touch or are apart).
+    pub fn overlap_area(&self, other: &Rectangle) -> f64 {
+        let x_overlap = (self.x + self.width).min(other.x + other.width) - self.x.max(other.x);
+        let y_overlap = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+
+        x_overlap.max(0.0) * y_overlap.max(0.0)
+    }
+
+    /// Edge segment shared by two adjacent rectangles, or `None` when they are not adjacent.
+    pub fn shared_edge(&self, other: &Rectangle) -> Option<Segment> {
+        if !self.is_adjacent_to(other) {
+            return None;
+        }
+
+        let vertical_edge_touching = self.x == other.x + other.width || self.x + self.width == other.x;
+        if vertical_edge_touching {
+            let x = if self.x == other.x + other.width { self.x } else { self.x + self.width };
+            let y1 = self.y.max(other.y);
+            let y2 = (self.y + self.height).min(other.y + other.height);
+            Some(Segment { x1: x, y1, x2: x, y2 })
+        } else {
+            let y = if self.y == other.y + other.height { self.y } else { self.y + self.height };
+            let x1 = self.x.max(other.x);
+            let x2 = (self.x + self.width).min(other.x + other.width);
+            Some(Segment { x1, y1: y, x2, y2: y })
+        }
+    }
+
+    /// Length of the edge segment shared by two adjacent rectangles.
+    /// Returns 0.0 when the rectangles are not adjacent.
+    pub fn shared_edge_length(&self, other: &Rectangle) -> f64 {
+        self.shared_edge(other).map_or(0.0, |segment| segment.length())
+    }
+
+    /// Centre point of the rectangle.
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Straight-line distance between the centres of two rectangles.
+    pub fn center_distance(&self, other: &Rectangle) -> f64 {
+        let (x1, y1) = self.center();
+        let (x2, y2) = other.center();
+        (x2 - x1).hypot(y2 - y1)
+    }
+
+    /// Shortest distance between the edges of two rectangles (0.0 when they touch or overlap).
+    pub fn edge_distance(&self, other: &Rectangle) -> f64 {
+        let x_gap = (other.x - (self.x + self.width)).max(self.x - (other.x + other.width)).max(0.0);
+        let y_gap = (other.y - (self.y + self.height)).max(self.y - (other.y + other.height)).max(0.0);
+        x_gap.hypot(y_gap)
+    }
+
     // Check if a rectangle is fully inside boundary.
     pub fn is_within_boundary(&self, boundary_width: f64, boundary_height: f64) -> bool {
         self.x >= 0.0 && (self.x + self.width) <= boundary_width &&
@@ -65,6 +140,263 @@ impl Rectangle {
         self.x == 0.0 || (self.x + self.width) == boundary_width ||
         self.y == 0.0 || (self.y + self.height) == boundary_height
     }
+
+    /// Check if a rectangle touches a specific side of the boundary.
+    pub fn touches_side(&self, side: BoundarySide, boundary_width: f64, boundary_height: f64) -> bool {
+        match side {
+            BoundarySide::Top => self.y == 0.0,
+            BoundarySide::Bottom => self.y + self.height == boundary_height,
+            BoundarySide::Left => self.x == 0.0,
+            BoundarySide::Right => self.x + self.width == boundary_width,
+        }
+    }
+
+    /// Part of the rectangle's edge lying on a side of the boundary, or `None` when it does not touch it.
+    pub fn exterior_contact(&self, side: BoundarySide, boundary_width: f64, boundary_height: f64) -> Option<Segment> {
+        if !self.touches_side(side, boundary_width, boundary_height) {
+            return None;
+        }
+
+        let (start, end, limit) = match side {
+            BoundarySide::Top | BoundarySide::Bottom => (self.x, self.x + self.width, boundary_width),
+            BoundarySide::Left | BoundarySide::Right => (self.y, self.y + self.height, boundary_height),
+        };
+        let start = start.max(0.0);
+        let end = end.min(limit).max(start);
+        Some(match side {
+            BoundarySide::Top => Segment { x1: start, y1: 0.0, x2: end, y2: 0.0 },
+            BoundarySide::Bottom => Segment { x1: start, y1: boundary_height, x2: end, y2: boundary_height },
+            BoundarySide::Left => Segment { x1: 0.0, y1: start, x2: 0.0, y2: end },
+            BoundarySide::Right => Segment { x1: boundary_width, y1: start, x2: boundary_width, y2: end },
+        })
+    }
+
+    /// Length of the rectangle's edge lying on a side of the boundary (0.0 when it does not touch it).
+    pub fn exterior_contact_length(&self, side: BoundarySide, boundary_width: f64, boundary_height: f64) -> f64 {
+        self.exterior_contact(side, boundary_width, boundary_height)
+            .map_or(0.0, |segment| segment.length())
+    }
+
+    /// Exterior contact length on each side of the boundary the rectangle touches.
+    pub fn exterior_contacts(&self, boundary_width: f64, boundary_height: f64) -> Vec<(BoundarySide, f64)> {
+        BoundarySide::ALL
+            .iter()
+            .map(|side| (*side, self.exterior_contact_length(*side, boundary_width, boundary_height)))
+            .filter(|(_, length)| *length > 0.0)
+            .collect()
+    }
+
+    /// Check if a rectangle lies entirely on the given side of another.
+    pub fn is_positioned(&self, position: RelativePosition, other: &Rectangle) -> bool {
+        match position {
+            RelativePosition::Above => self.y + self.height <= other.y,
+            RelativePosition::Below => self.y >= other.y + other.height,
+            RelativePosition::LeftOf => self.x + self.width <= other.x,
+            RelativePosition::RightOf => self.x >= other.x + other.width,
+        }
+    }
+
+    /// Check if a point lies inside the rectangle or on its outline.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+
+    /// Reflection of the rectangle across a centre line of the boundary.
+    pub fn mirrored(&self, axis: MirrorAxis, boundary_width: f64, boundary_height: f64) -> Rectangle {
+        match axis {
+            MirrorAxis::Vertical => Rectangle { x: boundary_width - self.x - self.width, ..*self },
+            MirrorAxis::Horizontal => Rectangle { y: boundary_height - self.y - self.height, ..*self },
+        }
+    }
+
+    /// Check if this rectangle shares the given dimension or edge line with another.
+    pub fn is_aligned_with(&self, alignment: Alignment, other: &Rectangle) -> bool {
+        match alignment {
+            Alignment::SameWidth => self.width == other.width,
+            Alignment::SameDepth => self.height == other.height,
+            Alignment::Top => self.y == other.y,
+            Alignment::Bottom => self.y + self.height == other.y + other.height,
+            Alignment::Left => self.x == other.x,
+            Alignment::Right => self.x + self.width == other.x + other.width,
+        }
+    }
+}
+
+/// Find the maximal rectangles of the boundary not covered by `occupied`.
+///
+/// A free rectangle is maximal when it cannot be extended on any side
+/// without leaving the boundary or overlapping an occupied rectangle. The
+/// result may overlap; together the rectangles cover all free space. Edges
+/// of maximal rectangles always lie on the boundary or an occupied edge, so
+/// only those coordinates are tried.
+pub fn maximal_free_rectangles(occupied: &[Rectangle], boundary_width: f64, boundary_height: f64) -> Vec<Rectangle> {
+    let coordinates = |limit: f64, edges: &dyn Fn(&Rectangle) -> [f64; 2]| {
+        let mut values = vec![0.0, limit];
+        values.extend(occupied.iter().flat_map(edges).filter(|v| *v > 0.0 && *v < limit));
+        values.sort_by(f64::total_cmp);
+        values.dedup();
+        values
+    };
+    let xs = coordinates(boundary_width, &|rect| [rect.x, rect.x + rect.width]);
+    let ys = coordinates(boundary_height, &|rect| [rect.y, rect.y + rect.height]);
+
+    let is_free = |rect: &Rectangle| !occupied.iter().any(|other| rect.overlaps_with(other));
+    let span = |x1: usize, x2: usize, y1: usize, y2: usize| Rectangle {
+        x: xs[x1],
+        y: ys[y1],
+        width: xs[x2] - xs[x1],
+        height: ys[y2] - ys[y1],
+    };
+
+    let mut maximal = Vec::new();
+    for x1 in 0..xs.len() {
+        for x2 in x1 + 1..xs.len() {
+            for y1 in 0..ys.len() {
+                for y2 in y1 + 1..ys.len() {
+                    if !is_free(&span(x1, x2, y1, y2)) {
+                        // Taller spans from the same top edge overlap too
+                        break;
+                    }
+                    let extendable = (x1 > 0 && is_free(&span(x1 - 1, x1, y1, y2)))
+                        || (x2 + 1 < xs.len() && is_free(&span(x2, x2 + 1, y1, y2)))
+                        || (y1 > 0 && is_free(&span(x1, x2, y1 - 1, y1)))
+                        || (y2 + 1 < ys.len() && is_free(&span(x1, x2, y2, y2 + 1)));
+                    if !extendable {
+                        maximal.push(span(x1, x2, y1, y2));
+                    }
+                }
+            }
+        }
+    }
+
+    maximal
+}
+
+/// Check if the rectangles form one connected region, joined along shared edges or overlaps.
+pub fn is_connected(rects: &[Rectangle]) -> bool {
+    if rects.is_empty() {
+        return true;
+    }
+
+    let mut visited = vec![false; rects.len()];
+    let mut stack = vec![0];
+    visited[0] = true;
+    while let Some(current) = stack.pop() {
+        for next in 0..rects.len() {
+            if !visited[next] && (rects[current].is_adjacent_to(&rects[next]) || rects[current].overlaps_with(&rects[next])) {
+                visited[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    visited.iter().all(|v| *v)
+}
+
+/// A grid of square cells laid over the boundary.
+///
+/// The last row and column are clipped to the boundary when its size is
+/// not a multiple of `cell_size`.
+#[derive(Debug, Clone, Copy)]
+pub struct CellGrid {
+    pub columns: usize,
+    pub rows: usize,
+    pub cell_size: f64,
+    pub boundary_width: f64,
+    pub boundary_height: f64,
+}
+
+impl CellGrid {
+    /// Cover the boundary with cells of the given size.
+    pub fn new(boundary_width: f64, boundary_height: f64, cell_size: f64) -> Self {
+        Self {
+            columns: (boundary_width / cell_size - 1e-9).ceil().max(0.0) as usize,
+            rows: (boundary_height / cell_size - 1e-9).ceil().max(0.0) as usize,
+            cell_size,
+            boundary_width,
+            boundary_height,
+        }
+    }
+
+    fn column_x(&self, i: usize) -> f64 {
+        (i as f64 * self.cell_size).min(self.boundary_width)
+    }
+
+    fn row_y(&self, j: usize) -> f64 {
+        (j as f64 * self.cell_size).min(self.boundary_height)
+    }
+
+    /// The rectangle spanning columns `i..end_i` and rows `j..end_j`.
+    pub fn span(&self, i: usize, j: usize, end_i: usize, end_j: usize) -> Rectangle {
+        Rectangle {
+            x: self.column_x(i),
+            y: self.row_y(j),
+            width: self.column_x(end_i) - self.column_x(i),
+            height: self.row_y(end_j) - self.row_y(j),
+        }
+    }
+
+    /// Check if the centre of a cell lies inside any of the rectangles.
+    pub fn is_covered(&self, i: usize, j: usize, rects: &[Rectangle]) -> bool {
+        let (center_x, center_y) = self.span(i, j, i + 1, j + 1).center();
+        rects.iter().any(|rect| {
+            center_x > rect.x && center_x < rect.x + rect.width && center_y > rect.y && center_y < rect.y + rect.height
+        })
+    }
+
+    /// Merge the selected cells into disjoint rectangles.
+    ///
+    /// Selected cells are joined into horizontal runs per row, and runs
+    /// spanning the same columns in consecutive rows into one rectangle.
+    pub fn merge_cells(&self, is_selected: impl Fn(usize, usize) -> bool) -> Vec<Rectangle> {
+        let mut rects: Vec<Rectangle> = Vec::new();
+        // Runs of the previous row as (first column, end column, index into rects)
+        let mut open_runs: Vec<(usize, usize, usize)> = Vec::new();
+
+        for j in 0..self.rows {
+            let mut runs = Vec::new();
+            let mut i = 0;
+            while i < self.columns {
+                if !is_selected(i, j) {
+                    i += 1;
+                    continue;
+                }
+                let start = i;
+                while i < self.columns && is_selected(i, j) {
+                    i += 1;
+                }
+                runs.push((start, i));
+            }
+
+            let mut next_open = Vec::new();
+            for (start, end) in runs {
+                match open_runs.iter().find(|(open_start, open_end, _)| *open_start == start && *open_end == end) {
+                    Some(&(_, _, index)) => {
+                        rects[index].height += self.row_y(j + 1) - self.row_y(j);
+                        next_open.push((start, end, index));
+                    }
+                    None => {
+                        rects.push(self.span(start, j, end, j + 1));
+                        next_open.push((start, end, rects.len() - 1));
+                    }
+                }
+            }
+            open_runs = next_open;
+        }
+
+        rects
+    }
+}
+
+/// Split the part of the boundary not covered by `occupied` into disjoint rectangles.
+pub fn free_space_partition(
+    occupied: &[Rectangle],
+    boundary_width: f64,
+    boundary_height: f64,
+    cell_size: f64,
+) -> Vec<Rectangle> {
+    let grid = CellGrid::new(boundary_width, boundary_height, cell_size);
+    grid.merge_cells(|i, j| !grid.is_covered(i, j, occupied))
 }
 
 #[cfg(test)]
@@ -232,6 +564,179 @@ mod tests {
         assert_eq!(rectangle.width, 3.0);
         assert_eq!(rectangle.height, 4.0);
     }
-    
-}
 
+    // Test 19: test_overlap_area_of_partial_overlap
+    // Checks the shared area of two partially overlapping rectangles.
+    #[test]
+    fn test_overlap_area_of_partial_overlap() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 10.0, height: 10.0 };
+        let rectangle2 = Rectangle { x: 5.0, y: 5.0, width: 10.0, height: 10.0 };
+        assert_eq!(rectangle1.overlap_area(&rectangle2), 25.0);
+    }
+
+    // Test 20: test_overlap_area_zero_when_touching
+    // Checks that touching rectangles have no shared area.
+    #[test]
+    fn test_overlap_area_zero_when_touching() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 5.0, height: 5.0 };
+        let rectangle2 = Rectangle { x: 5.0, y: 0.0, width: 5.0, height: 5.0 };
+        assert_eq!(rectangle1.overlap_area(&rectangle2), 0.0);
+    }
+
+    // Test 21: test_shared_edge_length_of_adjacent_rectangles
+    // Checks the length of the shared wall between two adjacent rectangles.
+    #[test]
+    fn test_shared_edge_length_of_adjacent_rectangles() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 5.0, height: 5.0 };
+        let rectangle2 = Rectangle { x: 5.0, y: 2.0, width: 10.0, height: 10.0 };
+        assert_eq!(rectangle1.shared_edge_length(&rectangle2), 3.0);
+    }
+
+    // Test 22: test_shared_edge_segment_on_horizontal_edge
+    // Checks the endpoints of the wall shared by two vertically stacked rectangles.
+    #[test]
+    fn test_shared_edge_segment_on_horizontal_edge() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 5.0, height: 5.0 };
+        let rectangle2 = Rectangle { x: 2.0, y: 5.0, width: 5.0, height: 3.0 };
+        let segment = rectangle1.shared_edge(&rectangle2).unwrap();
+        assert_eq!(segment, Segment { x1: 2.0, y1: 5.0, x2: 5.0, y2: 5.0 });
+        assert_eq!(segment.midpoint(), (3.5, 5.0));
+    }
+
+    // Test 23: test_center_and_edge_distance
+    // Checks centre-to-centre and edge-to-edge distances between separated rectangles.
+    #[test]
+    fn test_center_and_edge_distance() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 2.0, height: 2.0 };
+        let rectangle2 = Rectangle { x: 5.0, y: 6.0, width: 2.0, height: 2.0 };
+        assert_eq!(rectangle1.center_distance(&rectangle2), 7.810249675906654);
+        assert_eq!(rectangle1.edge_distance(&rectangle2), 5.0);
+    }
+
+    // Test 24: test_edge_distance_zero_when_touching
+    // Checks that touching rectangles are zero distance apart edge to edge.
+    #[test]
+    fn test_edge_distance_zero_when_touching() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 5.0, height: 5.0 };
+        let rectangle2 = Rectangle { x: 5.0, y: 2.0, width: 5.0, height: 5.0 };
+        assert_eq!(rectangle1.edge_distance(&rectangle2), 0.0);
+    }
+
+    // Test 25: test_touches_side_only_for_that_side
+    // Checks that a rectangle in the bottom-left corner touches only the bottom and left sides.
+    #[test]
+    fn test_touches_side_only_for_that_side() {
+        let rectangle = Rectangle { x: 0.0, y: 6.0, width: 4.0, height: 4.0 };
+        assert!(rectangle.touches_side(BoundarySide::Bottom, 10.0, 10.0));
+        assert!(rectangle.touches_side(BoundarySide::Left, 10.0, 10.0));
+        assert!(!rectangle.touches_side(BoundarySide::Top, 10.0, 10.0));
+        assert!(!rectangle.touches_side(BoundarySide::Right, 10.0, 10.0));
+    }
+
+    // Test 26: test_is_positioned_requires_full_separation
+    // Checks relative positions hold only when the rectangles do not overlap along that axis.
+    #[test]
+    fn test_is_positioned_requires_full_separation() {
+        let rectangle1 = Rectangle { x: 0.0, y: 0.0, width: 4.0, height: 3.0 };
+        let rectangle2 = Rectangle { x: 2.0, y: 3.0, width: 4.0, height: 3.0 };
+        assert!(rectangle1.is_positioned(RelativePosition::Above, &rectangle2));
+        assert!(rectangle2.is_positioned(RelativePosition::Below, &rectangle1));
+        assert!(!rectangle1.is_positioned(RelativePosition::LeftOf, &rectangle2));
+        assert!(!rectangle2.is_positioned(RelativePosition::RightOf, &rectangle1));
+    }
+
+    // Test 27: test_exterior_contacts_in_corner
+    // Checks that a corner rectangle reports contact along both boundary sides it touches.
+    #[test]
+    fn test_exterior_contacts_in_corner() {
+        let rectangle = Rectangle { x: 6.0, y: 7.0, width: 4.0, height: 3.0 };
+        assert_eq!(
+            rectangle.exterior_contacts(10.0, 10.0),
+            vec![(BoundarySide::Right, 3.0), (BoundarySide::Bottom, 4.0)]
+        );
+        assert_eq!(rectangle.exterior_contact_length(BoundarySide::Top, 10.0, 10.0), 0.0);
+    }
+
+    // Test 28: test_is_aligned_with_edges_and_dimensions
+    // Checks each alignment kind against two rooms in a row.
+    #[test]
+    fn test_is_aligned_with_edges_and_dimensions() {
+        // Two bedrooms in a row along the top, same depth but different widths
+        let bedroom1 = Rectangle { x: 0.0, y: 0.0, width: 3.0, height: 4.0 };
+        let bedroom2 = Rectangle { x: 3.0, y: 0.0, width: 3.5, height: 4.0 };
+
+        assert!(bedroom1.is_aligned_with(Alignment::SameDepth, &bedroom2));
+        assert!(bedroom1.is_aligned_with(Alignment::Top, &bedroom2));
+        assert!(bedroom1.is_aligned_with(Alignment::Bottom, &bedroom2));
+        assert!(!bedroom1.is_aligned_with(Alignment::SameWidth, &bedroom2));
+        assert!(!bedroom1.is_aligned_with(Alignment::Left, &bedroom2));
+    }
+
+    // Test 29: test_mirrored_across_each_axis
+    // Checks that a rectangle reflects across the boundary centre lines.
+    #[test]
+    fn test_mirrored_across_each_axis() {
+        let rectangle = Rectangle { x: 1.0, y: 1.0, width: 3.0, height: 4.0 };
+
+        let vertical = rectangle.mirrored(MirrorAxis::Vertical, 10.0, 8.0);
+        assert_eq!((vertical.x, vertical.y, vertical.width, vertical.height), (6.0, 1.0, 3.0, 4.0));
+
+        let horizontal = rectangle.mirrored(MirrorAxis::Horizontal, 10.0, 8.0);
+        assert_eq!((horizontal.x, horizontal.y, horizontal.width, horizontal.height), (1.0, 3.0, 3.0, 4.0));
+    }
+
+    // Test 30: test_free_space_partition_covers_gaps
+    // Checks that the uncovered L-shaped area is split into disjoint rectangles of the right total area.
+    #[test]
+    fn test_free_space_partition_covers_gaps() {
+        let occupied = vec![Rectangle { x: 0.0, y: 0.0, width: 6.0, height: 4.0 }];
+        let free = free_space_partition(&occupied, 10.0, 6.0, 0.5);
+
+        let total: f64 = free.iter().map(|r| r.width * r.height).sum();
+        assert_eq!(total, 60.0 - 24.0);
+        assert_eq!(free.len(), 2);
+        for (i, a) in free.iter().enumerate() {
+            assert!(!a.overlaps_with(&occupied[0]));
+            for b in free.iter().skip(i + 1) {
+                assert!(!a.overlaps_with(b));
+            }
+        }
+    }
+
+    // Test 31: test_maximal_free_rectangles_around_room
+    // Checks that free space around a corner room yields the two maximal strips.
+    #[test]
+    fn test_maximal_free_rectangles_around_room() {
+        let occupied = vec![Rectangle { x: 0.0, y: 0.0, width: 6.0, height: 4.0 }];
+        let maximal = maximal_free_rectangles(&occupied, 10.0, 6.0);
+
+        assert_eq!(maximal.len(), 2);
+        assert!(maximal.contains(&Rectangle { x: 6.0, y: 0.0, width: 4.0, height: 6.0 }));
+        assert!(maximal.contains(&Rectangle { x: 0.0, y: 4.0, width: 10.0, height: 2.0 }));
+    }
+
+    // Test 32: test_is_connected_requires_shared_edges
+    // Checks that rectangles touching only at a corner are not connected.
+    #[test]
+    fn test_is_connected_requires_shared_edges() {
+        let a = Rectangle { x: 0.0, y: 0.0, width: 2.0, height: 2.0 };
+        let b = Rectangle { x: 2.0, y: 0.0, width: 2.0, height: 2.0 };
+        let corner = Rectangle { x: 4.0, y: 2.0, width: 2.0, height: 2.0 };
+
+        assert!(is_connected(&[a, b]));
+        assert!(!is_connected(&[a, b, corner]));
+    }
+
+    // Test 33: test_exterior_contact_segment
+    // Checks that the exterior contact is the part of the edge on the boundary side.
+    #[test]
+    fn test_exterior_contact_segment() {
+        let rect = Rectangle { x: 6.0, y: 2.0, width: 4.0, height: 3.0 };
+
+        assert_eq!(
+            rect.exterior_contact(BoundarySide::Right, 10.0, 8.0),
+            Some(Segment { x1: 10.0, y1: 2.0, x2: 10.0, y2: 5.0 })
+        );
+        assert_eq!(rect.exterior_contact(BoundarySide::Top, 10.0, 8.0), None);
+    }
+}
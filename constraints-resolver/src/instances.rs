@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use crate::types::{AdjacencyStrength, RoomRequirement};
+
+/// Expand multi-count requirements into one requirement per room instance.
+///
+/// A requirement with a single instance keeps its id. Otherwise instances
+/// are named `{id}_1`, `{id}_2`, ... and instances past `required_count()`
+/// are marked optional. References to the original id in other
+/// requirements are repeated per instance: `not_adjacent_to` and `Avoid` or
+/// `Forbidden` relations for every instance, and all other references for
+/// the required instances only (every instance when none is required), so
+/// "adjacent to bedroom" does not hinge on optional bedrooms.
+///
+/// Generated ids may collide with ids the caller chose, so the result
+/// should be validated again.
+pub fn expand_instances(room_requirements: &[RoomRequirement]) -> Vec<RoomRequirement> {
+    let mut all_ids: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut wanted_ids: HashMap<&str, Vec<String>> = HashMap::new();
+    for room_req in room_requirements {
+        let count = room_req.instance_count();
+        let ids: Vec<String> = if count == 1 {
+            vec![room_req.id.clone()]
+        } else {
+            (1..=count).map(|n| format!("{}_{}", room_req.id, n)).collect()
+        };
+        let required = room_req.required_count().min(ids.len());
+        let wanted = if required == 0 { ids.clone() } else { ids[..required].to_vec() };
+        all_ids.insert(room_req.id.as_str(), ids);
+        wanted_ids.insert(room_req.id.as_str(), wanted);
+    }
+
+    let mut instances = Vec::new();
+    for room_req in room_requirements {
+        let required_count = room_req.required_count();
+
+        for (index, instance_id) in all_ids[room_req.id.as_str()].iter().enumerate() {
+            let mut instance = room_req.clone();
+            instance.id = instance_id.clone();
+            instance.min_count = None;
+            instance.max_count = None;
+            instance.optional = index >= required_count;
+
+            instance.adjacent_to = expand_references(&room_req.adjacent_to, instance_id, &wanted_ids, |id| id);
+            instance.not_adjacent_to = expand_references(&room_req.not_adjacent_to, instance_id, &all_ids, |id| id);
+            instance.relations = room_req
+                .relations
+                .iter()
+                .flat_map(|relation| {
+                    let ids = match relation.strength {
+                        AdjacencyStrength::Avoid | AdjacencyStrength::Forbidden => &all_ids,
+                        _ => &wanted_ids,
+                    };
+                    expand_references(std::slice::from_ref(relation), instance_id, ids, |r| &mut r.room_id)
+                })
+                .collect();
+            instance.distance_constraints =
+                expand_references(&room_req.distance_constraints, instance_id, &wanted_ids, |c| &mut c.room_id);
+            instance.position_constraints =
+                expand_references(&room_req.position_constraints, instance_id, &wanted_ids, |c| &mut c.room_id);
+
+            instances.push(instance);
+        }
+    }
+
+    instances
+}
+
+/// Repeat each constraint once per instance of the room it references.
+fn expand_references<T: Clone>(
+    constraints: &[T],
+    own_id: &str,
+    instance_ids: &HashMap<&str, Vec<String>>,
+    room_id: impl Fn(&mut T) -> &mut String,
+) -> Vec<T> {
+    let mut expanded = Vec::new();
+    for constraint in constraints {
+        let mut constraint = constraint.clone();
+        let referenced = room_id(&mut constraint).clone();
+        let Some(ids) = instance_ids.get(referenced.as_str()) else {
+            expanded.push(constraint);
+            continue;
+        };
+
+        for id in ids.iter().filter(|id| *id != own_id) {
+            let mut instance_constraint = constraint.clone();
+            *room_id(&mut instance_constraint) = id.clone();
+            expanded.push(instance_constraint);
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test 1: test_single_rooms_keep_their_ids
+    #[test]
+    fn test_single_rooms_keep_their_ids() {
+        let rooms = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 20.0, ..Default::default() },
+            RoomRequirement { id: "study".to_string(), min_area: 8.0, optional: true, ..Default::default() },
+        ];
+        let instances = expand_instances(&rooms);
+
+        let summary: Vec<(&str, bool)> = instances.iter().map(|r| (r.id.as_str(), r.optional)).collect();
+        assert_eq!(summary, vec![("living", false), ("study", true)]);
+    }
+
+    // Test 2: test_counts_expand_into_instances_and_references
+    #[test]
+    fn test_counts_expand_into_instances_and_references() {
+        let rooms = vec![
+            RoomRequirement { id: "hall".to_string(), min_area: 6.0, adjacent_to: vec!["closet".to_string()], ..Default::default() },
+            RoomRequirement {
+                id: "closet".to_string(),
+                min_area: 1.0,
+                min_count: Some(2),
+                max_count: Some(4),
+                ..Default::default()
+            },
+        ];
+        let instances = expand_instances(&rooms);
+
+        let summary: Vec<(&str, bool)> = instances.iter().map(|r| (r.id.as_str(), r.optional)).collect();
+        assert_eq!(
+            summary,
+            vec![("hall", false), ("closet_1", false), ("closet_2", false), ("closet_3", true), ("closet_4", true)]
+        );
+        assert_eq!(instances[0].adjacent_to, vec!["closet_1", "closet_2"]);
+    }
+
+    // Test 3: test_avoided_references_cover_optional_instances
+    #[test]
+    fn test_avoided_references_cover_optional_instances() {
+        use crate::validation::{validate_requirements, ValidationError};
+
+        let rooms = vec![
+            RoomRequirement {
+                id: "living".to_string(),
+                min_area: 20.0,
+                adjacent_to: vec!["bedroom".to_string()],
+                not_adjacent_to: vec!["study".to_string()],
+                ..Default::default()
+            },
+            RoomRequirement {
+                id: "bedroom".to_string(),
+                min_area: 9.0,
+                min_count: Some(2),
+                max_count: Some(3),
+                ..Default::default()
+            },
+            RoomRequirement {
+                id: "study".to_string(),
+                min_area: 6.0,
+                max_count: Some(2),
+                optional: true,
+                ..Default::default()
+            },
+            RoomRequirement { id: "bedroom_1".to_string(), min_area: 9.0, ..Default::default() },
+        ];
+        let instances = expand_instances(&rooms);
+
+        // Only the two required bedrooms must be adjacent; every optional study is avoided
+        assert_eq!(instances[0].adjacent_to, vec!["bedroom_1", "bedroom_2"]);
+        assert_eq!(instances[0].not_adjacent_to, vec!["study_1", "study_2"]);
+
+        // The generated bedroom_1 collides with the room already called that
+        assert_eq!(
+            validate_requirements(&instances),
+            Err(vec![ValidationError::DuplicateId { room_id: "bedroom_1".to_string() }])
+        );
+    }
+}
@@ -1,7 +1,27 @@
 pub mod types;
+pub mod adjacency_graph;
+pub mod annealing;
+pub mod config;
 pub mod geometry;
+pub mod distance;
+pub mod doors;
 pub mod candidate_generation;
+pub mod circulation;
+pub mod feasibility;
+pub mod fill;
+pub mod free_space;
+pub mod instances;
+pub mod planarity;
+pub mod rectangular_dual;
 pub mod scoring;
+pub mod sequence_pair;
+pub mod slicing_tree;
+pub mod violation;
 pub mod room_ordering;
 pub mod solver;
+pub mod validation;
+pub mod wall_graph;
+pub mod walls;
 pub mod wasm;
+pub mod windows;
+pub mod zoning;
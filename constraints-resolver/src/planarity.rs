@@ -0,0 +1,333 @@
+use std::collections::{HashSet, VecDeque};
+
+/// Check whether an undirected graph can be drawn in the plane without crossings.
+///
+/// Vertices are `0..vertex_count`; repeated edges and loops are ignored.
+/// Each biconnected component is tested on its own with the path-addition
+/// algorithm of Demoucron, Malgrange and Pertuiset.
+pub fn is_planar(vertex_count: usize, edges: &[(usize, usize)]) -> bool {
+    let mut adjacency = vec![Vec::new(); vertex_count];
+    for &(u, v) in edges {
+        if u != v && !adjacency[u].contains(&v) {
+            adjacency[u].push(v);
+            adjacency[v].push(u);
+        }
+    }
+
+    biconnected_components(&adjacency).iter().all(|component| is_component_planar(component))
+}
+
+/// Edge sets of the biconnected components, found with Tarjan's low-point search.
+fn biconnected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<(usize, usize)>> {
+    struct Search<'a> {
+        adjacency: &'a [Vec<usize>],
+        discovery: Vec<Option<usize>>,
+        low: Vec<usize>,
+        time: usize,
+        edges: Vec<(usize, usize)>,
+        components: Vec<Vec<(usize, usize)>>,
+    }
+
+    fn visit(search: &mut Search, u: usize, parent: Option<usize>) {
+        let discovered = search.time;
+        search.discovery[u] = Some(discovered);
+        search.low[u] = discovered;
+        search.time += 1;
+
+        let adjacency = search.adjacency;
+        for &v in adjacency[u].iter() {
+            match search.discovery[v] {
+                None => {
+                    search.edges.push((u, v));
+                    visit(search, v, Some(u));
+                    search.low[u] = search.low[u].min(search.low[v]);
+                    // u separates v's subtree: its edges form a component
+                    if search.low[v] >= discovered {
+                        let mut component = Vec::new();
+                        while let Some(edge) = search.edges.pop() {
+                            component.push(edge);
+                            if edge == (u, v) {
+                                break;
+                            }
+                        }
+                        search.components.push(component);
+                    }
+                }
+                Some(v_discovered) if Some(v) != parent && v_discovered < discovered => {
+                    search.edges.push((u, v));
+                    search.low[u] = search.low[u].min(v_discovered);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut search = Search {
+        adjacency,
+        discovery: vec![None; adjacency.len()],
+        low: vec![0; adjacency.len()],
+        time: 0,
+        edges: Vec::new(),
+        components: Vec::new(),
+    };
+    for vertex in 0..adjacency.len() {
+        if search.discovery[vertex].is_none() {
+            visit(&mut search, vertex, None);
+        }
+    }
+    search.components
+}
+
+/// A piece of the graph not yet embedded: a single edge between embedded
+/// vertices, or a connected set of unembedded vertices with their edges.
+struct Fragment {
+    interior: Vec<usize>,
+    attachments: Vec<usize>,
+}
+
+/// Demoucron-Malgrange-Pertuiset planarity test of one biconnected component.
+///
+/// Starts from a cycle, then repeatedly embeds a path through a fragment
+/// into a face that holds all of the fragment's attachments, preferring
+/// fragments that fit only one face. A fragment that fits no face proves
+/// the component is not planar.
+fn is_component_planar(edges: &[(usize, usize)]) -> bool {
+    // Renumber the component's vertices from 0
+    let mut vertices: Vec<usize> = edges.iter().flat_map(|&(u, v)| [u, v]).collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    let index = |vertex: usize| vertices.binary_search(&vertex).unwrap_or_default();
+    let mut adjacency = vec![Vec::new(); vertices.len()];
+    for &(u, v) in edges {
+        adjacency[index(u)].push(index(v));
+        adjacency[index(v)].push(index(u));
+    }
+
+    // Every graph with at most four vertices is planar; Euler's formula bounds the rest
+    let (vertex_count, edge_count) = (vertices.len(), edges.len());
+    if vertex_count < 5 {
+        return true;
+    }
+    if edge_count > 3 * vertex_count - 6 {
+        return false;
+    }
+
+    let cycle = find_cycle(&adjacency);
+    let mut embedded_vertices = vec![false; vertex_count];
+    let mut embedded_edges = HashSet::new();
+    embed_path(&cycle, &mut embedded_vertices, &mut embedded_edges);
+    embedded_edges.insert(edge_key(cycle[0], cycle[cycle.len() - 1]));
+    let mut faces = vec![cycle.clone(), cycle];
+
+    loop {
+        let fragments = fragments(&adjacency, &embedded_vertices, &embedded_edges);
+        if fragments.is_empty() {
+            return true;
+        }
+
+        let mut chosen: Option<(&Fragment, usize)> = None;
+        for fragment in fragments.iter() {
+            let admissible: Vec<usize> = (0..faces.len())
+                .filter(|&face| fragment.attachments.iter().all(|vertex| faces[face].contains(vertex)))
+                .collect();
+            match admissible.len() {
+                0 => return false,
+                1 => chosen = Some((fragment, admissible[0])),
+                _ if chosen.is_none() => chosen = Some((fragment, admissible[0])),
+                _ => {}
+            }
+        }
+
+        let Some((fragment, face)) = chosen else { return false };
+        let path = fragment_path(fragment, &adjacency, &embedded_vertices);
+        embed_path(&path, &mut embedded_vertices, &mut embedded_edges);
+
+        let (first, second) = split_face(&faces[face], &path);
+        faces[face] = first;
+        faces.push(second);
+    }
+}
+
+fn edge_key(u: usize, v: usize) -> (usize, usize) {
+    (u.min(v), u.max(v))
+}
+
+/// A cycle through vertex 0, which a biconnected component always has.
+fn find_cycle(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let start = adjacency[0][0];
+    let mut previous = vec![None; adjacency.len()];
+    previous[start] = Some(start);
+    let mut queue = VecDeque::from([start]);
+    while let Some(u) = queue.pop_front() {
+        for &v in adjacency[u].iter() {
+            // Skip the edge from `start` straight back to vertex 0
+            if previous[v].is_some() || (u == start && v == 0) {
+                continue;
+            }
+            previous[v] = Some(u);
+            queue.push_back(v);
+        }
+    }
+
+    let mut cycle = vec![0];
+    let mut vertex = 0;
+    while vertex != start {
+        vertex = previous[vertex].unwrap_or(start);
+        cycle.push(vertex);
+    }
+    cycle
+}
+
+fn embed_path(path: &[usize], embedded_vertices: &mut [bool], embedded_edges: &mut HashSet<(usize, usize)>) {
+    for &vertex in path {
+        embedded_vertices[vertex] = true;
+    }
+    for pair in path.windows(2) {
+        embedded_edges.insert(edge_key(pair[0], pair[1]));
+    }
+}
+
+fn fragments(
+    adjacency: &[Vec<usize>],
+    embedded_vertices: &[bool],
+    embedded_edges: &HashSet<(usize, usize)>,
+) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+
+    // Edges between embedded vertices that are not embedded themselves
+    for (u, neighbours) in adjacency.iter().enumerate() {
+        for &v in neighbours {
+            if u < v && embedded_vertices[u] && embedded_vertices[v] && !embedded_edges.contains(&(u, v)) {
+                fragments.push(Fragment { interior: Vec::new(), attachments: vec![u, v] });
+            }
+        }
+    }
+
+    // Connected groups of unembedded vertices, attached where they meet embedded ones
+    let mut seen = embedded_vertices.to_vec();
+    for start in 0..adjacency.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut interior = vec![start];
+        let mut attachments = Vec::new();
+        let mut next = 0;
+        while next < interior.len() {
+            let u = interior[next];
+            next += 1;
+            for &v in adjacency[u].iter() {
+                if embedded_vertices[v] {
+                    if !attachments.contains(&v) {
+                        attachments.push(v);
+                    }
+                } else if !seen[v] {
+                    seen[v] = true;
+                    interior.push(v);
+                }
+            }
+        }
+        fragments.push(Fragment { interior, attachments });
+    }
+
+    fragments
+}
+
+/// A path through the fragment between two of its attachments.
+fn fragment_path(fragment: &Fragment, adjacency: &[Vec<usize>], embedded_vertices: &[bool]) -> Vec<usize> {
+    if fragment.interior.is_empty() {
+        return fragment.attachments.clone();
+    }
+
+    // Walk from a neighbour of the first attachment to a vertex touching another attachment
+    let from = fragment.attachments[0];
+    let Some(&start) = adjacency[from].iter().find(|v| fragment.interior.contains(v)) else {
+        return fragment.attachments.clone();
+    };
+    let mut previous = vec![None; adjacency.len()];
+    previous[start] = Some(start);
+    let mut queue = VecDeque::from([start]);
+    while let Some(u) = queue.pop_front() {
+        if let Some(&to) = adjacency[u].iter().find(|&&v| embedded_vertices[v] && v != from) {
+            let mut path = vec![to, u];
+            let mut vertex = u;
+            while vertex != start {
+                vertex = previous[vertex].unwrap_or(start);
+                path.push(vertex);
+            }
+            path.push(from);
+            path.reverse();
+            return path;
+        }
+        for &v in adjacency[u].iter() {
+            if !embedded_vertices[v] && previous[v].is_none() {
+                previous[v] = Some(u);
+                queue.push_back(v);
+            }
+        }
+    }
+    fragment.attachments.clone()
+}
+
+/// Split a face along a path between two of its vertices.
+fn split_face(face: &[usize], path: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let (from, to) = (path[0], path[path.len() - 1]);
+    let start = face.iter().position(|&vertex| vertex == from).unwrap_or_default();
+    let rotated: Vec<usize> = face[start..].iter().chain(face[..start].iter()).copied().collect();
+    let end = rotated.iter().position(|&vertex| vertex == to).unwrap_or_default();
+    let inner = &path[1..path.len() - 1];
+
+    // from .. to along the face, then back along the path
+    let mut first: Vec<usize> = rotated[..=end].to_vec();
+    first.extend(inner.iter().rev());
+    // to .. from along the face, then forward along the path
+    let mut second: Vec<usize> = rotated[end..].to_vec();
+    second.push(from);
+    second.extend(inner.iter());
+    (first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete_graph(vertex_count: usize) -> Vec<(usize, usize)> {
+        (0..vertex_count).flat_map(|u| (u + 1..vertex_count).map(move |v| (u, v))).collect()
+    }
+
+    // Test 1: test_kuratowski_graphs_are_not_planar
+    #[test]
+    fn test_kuratowski_graphs_are_not_planar() {
+        assert!(!is_planar(5, &complete_graph(5)));
+
+        let k33: Vec<(usize, usize)> = (0..3).flat_map(|u| (3..6).map(move |v| (u, v))).collect();
+        assert!(!is_planar(6, &k33));
+
+        // K3,3 with each edge subdivided passes Euler's bound but is still not planar
+        let subdivided: Vec<(usize, usize)> =
+            k33.iter().enumerate().flat_map(|(i, &(u, v))| [(u, 6 + i), (6 + i, v)]).collect();
+        assert!(!is_planar(15, &subdivided));
+    }
+
+    // Test 2: test_planar_graphs_are_accepted
+    #[test]
+    fn test_planar_graphs_are_accepted() {
+        assert!(is_planar(4, &complete_graph(4)));
+
+        // Octahedron: a maximal planar graph with 6 vertices and 12 edges
+        let octahedron: Vec<(usize, usize)> =
+            complete_graph(6).into_iter().filter(|&(u, v)| u % 3 != v % 3).collect();
+        assert_eq!(octahedron.len(), 12);
+        assert!(is_planar(6, &octahedron));
+
+        // Two K4s joined at a cut vertex, plus a pendant edge and a loop
+        let mut joined = complete_graph(4);
+        joined.extend(complete_graph(4).into_iter().map(|(u, v)| (u + 3, v + 3)));
+        joined.extend([(6, 7), (7, 7)]);
+        assert!(is_planar(8, &joined));
+
+        // K5 with one edge removed
+        let almost: Vec<(usize, usize)> = complete_graph(5).into_iter().skip(1).collect();
+        assert!(is_planar(5, &almost));
+    }
+}
@@ -0,0 +1,566 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::candidate_generation::GRID_SIZE;
+use crate::config::SolverConfig;
+use crate::planarity::is_planar;
+use crate::scoring::LayoutScore;
+use crate::types::{AdjacencyStrength, Room, RoomRequirement};
+
+/// Partial floorplans the search may visit before giving up.
+const SEARCH_LIMIT: usize = 200_000;
+
+/// Rounds of alternately sizing widths and heights when scaling a floorplan.
+const SCALING_ROUNDS: usize = 8;
+
+/// Why the rectangular dual strategy found no layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RectangularDualError {
+    /// The requested adjacency graph cannot be drawn without crossings, so no floorplan realises it.
+    NonPlanar,
+    /// No floorplan realises every requested adjacency while keeping forbidden pairs apart.
+    NoRectangularDual,
+    /// Floorplans realising the graph exist, but none could be scaled to the boundary
+    /// and the room requirements.
+    NotScalable { floorplans_tried: usize },
+    /// The search stopped at its limit before finding any floorplan.
+    SearchLimitReached,
+}
+
+impl fmt::Display for RectangularDualError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RectangularDualError::NonPlanar => write!(f, "The requested adjacency graph is not planar"),
+            RectangularDualError::NoRectangularDual => {
+                write!(f, "No rectangular floorplan realises every requested adjacency")
+            }
+            RectangularDualError::NotScalable { floorplans_tried } => write!(
+                f,
+                "None of the {} floorplans realising the adjacencies could be scaled to the boundary",
+                floorplans_tried
+            ),
+            RectangularDualError::SearchLimitReached => {
+                write!(f, "The search limit was reached before a floorplan was found")
+            }
+        }
+    }
+}
+
+/// A room of a floorplan under construction, in unit-square coordinates.
+///
+/// Coordinates are only ever copied between cells, never recomputed, so
+/// cells that share a wall compare equal on it exactly.
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    room: usize,
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+}
+
+impl Cell {
+    /// The same cell mirrored across the diagonal, swapping the axes.
+    fn transposed(&self) -> Cell {
+        Cell { room: self.room, left: self.top, top: self.left, right: self.bottom, bottom: self.right }
+    }
+}
+
+/// Lay out rooms as a rectangular dual of their requested adjacency graph.
+///
+/// The graph has an edge for every `Required` relation between the rooms; it
+/// must be planar. Preferred adjacencies are left to `evaluate` to score, like
+/// the other soft constraints, so they never rule a floorplan out. Floorplans are
+/// built by inserting one room at a time into the top-left corner, pushing
+/// either the first few rooms along the top down or those along the left
+/// side right. Every rectangular floorplan arises this way, and insertions
+/// never remove a shared wall, so branches that miss a requested adjacency
+/// or create a forbidden one are cut as soon as the room is inserted.
+///
+/// Each complete floorplan is scaled so the rooms exactly fill the boundary
+/// with walls on the grid, every room reaching its `min_area` and minimum
+/// dimensions inside its walls, and then handed to `evaluate`. The first
+/// valid layout is returned.
+pub fn solve_rectangular_dual(
+    rooms: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+    evaluate: &dyn Fn(&[Room]) -> LayoutScore,
+) -> Result<Vec<Room>, RectangularDualError> {
+    let index = |room_id: &str| rooms.iter().position(|room_req| room_req.id == room_id);
+    let mut requested = vec![vec![false; rooms.len()]; rooms.len()];
+    let mut forbidden = vec![vec![false; rooms.len()]; rooms.len()];
+    for (i, room_req) in rooms.iter().enumerate() {
+        for relation in room_req.adjacency_relations() {
+            let Some(j) = index(&relation.room_id) else { continue };
+            let pairs = match relation.strength {
+                AdjacencyStrength::Required => &mut requested,
+                AdjacencyStrength::Forbidden => &mut forbidden,
+                AdjacencyStrength::StronglyPreferred | AdjacencyStrength::Preferred | AdjacencyStrength::Avoid => {
+                    continue
+                }
+            };
+            pairs[i][j] = true;
+            pairs[j][i] = true;
+        }
+    }
+
+    let edges: Vec<(usize, usize)> = (0..rooms.len())
+        .flat_map(|i| (i + 1..rooms.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| requested[i][j])
+        .collect();
+    if !is_planar(rooms.len(), &edges) {
+        return Err(RectangularDualError::NonPlanar);
+    }
+    if rooms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut search = DualSearch {
+        rooms,
+        requested,
+        forbidden,
+        boundary_width,
+        boundary_height,
+        config,
+        evaluate,
+        visited: 0,
+        floorplans_tried: 0,
+    };
+    let mut placed = vec![false; rooms.len()];
+    match search.extend(&[], &mut placed) {
+        Some(layout) => Ok(layout),
+        None if search.floorplans_tried > 0 => {
+            Err(RectangularDualError::NotScalable { floorplans_tried: search.floorplans_tried })
+        }
+        None if search.visited >= SEARCH_LIMIT => Err(RectangularDualError::SearchLimitReached),
+        None => Err(RectangularDualError::NoRectangularDual),
+    }
+}
+
+struct DualSearch<'a> {
+    rooms: &'a [RoomRequirement],
+    requested: Vec<Vec<bool>>,
+    forbidden: Vec<Vec<bool>>,
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &'a SolverConfig,
+    evaluate: &'a dyn Fn(&[Room]) -> LayoutScore,
+    visited: usize,
+    floorplans_tried: usize,
+}
+
+impl DualSearch<'_> {
+    fn extend(&mut self, cells: &[Cell], placed: &mut [bool]) -> Option<Vec<Room>> {
+        if cells.len() == self.rooms.len() {
+            self.floorplans_tried += 1;
+            let layout = scale_floorplan(cells, self.rooms, self.boundary_width, self.boundary_height, self.config)?;
+            return (self.evaluate)(&layout).is_valid().then_some(layout);
+        }
+
+        // Rooms with the most requested neighbours already placed constrain the insertion most
+        let mut next_rooms: Vec<usize> = (0..self.rooms.len()).filter(|&room| !placed[room]).collect();
+        next_rooms.sort_by_key(|&room| {
+            Reverse((0..self.rooms.len()).filter(|&other| placed[other] && self.requested[room][other]).count())
+        });
+
+        for room in next_rooms {
+            if cells.is_empty() {
+                let whole = Cell { room, left: 0.0, top: 0.0, right: 1.0, bottom: 1.0 };
+                placed[room] = true;
+                let layout = self.extend(&[whole], placed);
+                placed[room] = false;
+                if layout.is_some() {
+                    return layout;
+                }
+                continue;
+            }
+
+            for along_top in [true, false] {
+                for count in 1..=cells.len() {
+                    if self.visited >= SEARCH_LIMIT {
+                        return None;
+                    }
+                    self.visited += 1;
+
+                    let Some((next, neighbours)) = insert_corner(cells, room, along_top, count) else { break };
+                    placed[room] = true;
+                    let layout = if self.is_consistent(&next, room, &neighbours, placed) {
+                        self.extend(&next, placed)
+                    } else {
+                        None
+                    };
+                    placed[room] = false;
+                    if layout.is_some() {
+                        return layout;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether the floorplan can still grow into one realising the requested graph.
+    ///
+    /// The inserted room must touch every placed room it is requested next to
+    /// and none it is forbidden from, and every room still waiting for a
+    /// neighbour must stay on the top or left side, where later rooms go.
+    fn is_consistent(&self, cells: &[Cell], room: usize, neighbours: &[usize], placed: &[bool]) -> bool {
+        let requested = &self.requested[room];
+        let forbidden = &self.forbidden[room];
+        let meets_placed = (0..self.rooms.len())
+            .filter(|&other| other != room && placed[other] && requested[other])
+            .all(|other| neighbours.contains(&other));
+        let avoids_forbidden = neighbours.iter().all(|&other| !forbidden[other]);
+        let reachable = cells.iter().all(|cell| {
+            let waiting = (0..self.rooms.len()).any(|other| !placed[other] && self.requested[cell.room][other]);
+            !waiting || cell.top == 0.0 || cell.left == 0.0
+        });
+        meets_placed && avoids_forbidden && reachable
+    }
+}
+
+/// Insert a room into the top-left corner of a floorplan.
+///
+/// Along the top, the new room takes the upper part of the first `count`
+/// rooms touching the top side, which move down; it then borders those
+/// rooms and the next room along the top. Along the left side the same
+/// happens with the axes swapped. Returns the new cells and the rooms the
+/// new room borders, or `None` when fewer than `count` rooms are on that side.
+fn insert_corner(cells: &[Cell], room: usize, along_top: bool, count: usize) -> Option<(Vec<Cell>, Vec<usize>)> {
+    if !along_top {
+        let transposed: Vec<Cell> = cells.iter().map(Cell::transposed).collect();
+        let (next, neighbours) = insert_corner(&transposed, room, true, count)?;
+        return Some((next.iter().map(Cell::transposed).collect(), neighbours));
+    }
+
+    let mut top_side: Vec<usize> = (0..cells.len()).filter(|&i| cells[i].top == 0.0).collect();
+    top_side.sort_by(|&a, &b| cells[a].left.total_cmp(&cells[b].left));
+    if count > top_side.len() {
+        return None;
+    }
+
+    let covered = &top_side[..count];
+    let depth = covered.iter().map(|&i| cells[i].bottom).fold(f64::INFINITY, f64::min) / 2.0;
+    let mut next = cells.to_vec();
+    for &i in covered {
+        next[i].top = depth;
+    }
+    next.push(Cell { room, left: 0.0, top: 0.0, right: cells[covered[count - 1]].right, bottom: depth });
+
+    let neighbours = top_side.iter().take(count + 1).map(|&i| cells[i].room).collect();
+    Some((next, neighbours))
+}
+
+/// Give a floorplan real dimensions inside the boundary.
+///
+/// Widths and heights are sized in turn: each room is made wide enough for
+/// `min_area` at its current height, then tall enough at its new width, with
+/// walls allowed for, until every room is large enough. Returns `None` when
+/// the floorplan cannot hold the rooms.
+fn scale_floorplan(
+    cells: &[Cell],
+    rooms: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> Option<Vec<Room>> {
+    let transposed: Vec<Cell> = cells.iter().map(Cell::transposed).collect();
+    let allowances: Vec<(f64, f64)> = cells
+        .iter()
+        .map(|cell| (wall_allowance(cell, config), wall_allowance(&cell.transposed(), config)))
+        .collect();
+    let needed = |net: f64, allowance: f64| round_up_to_grid(net + allowance).max(GRID_SIZE);
+
+    // Start from the narrowest rooms and let the boundary width spread them out
+    let mut widths: Vec<f64> = cells
+        .iter()
+        .zip(allowances.iter())
+        .map(|(cell, (allowance, _))| needed(rooms[cell.room].min_width.unwrap_or(0.0), *allowance))
+        .collect();
+
+    for _ in 0..SCALING_ROUNDS {
+        let columns = axis_positions(cells, &widths, boundary_width)?;
+        let heights: Vec<f64> = cells
+            .iter()
+            .zip(columns.iter().zip(allowances.iter()))
+            .map(|(cell, ((left, right), (allowance_x, allowance_y)))| {
+                let room_req = &rooms[cell.room];
+                let net_width = right - left - allowance_x;
+                if net_width <= 0.0 {
+                    return f64::INFINITY;
+                }
+                needed((room_req.min_area / net_width).max(room_req.min_depth.unwrap_or(0.0)), *allowance_y)
+            })
+            .collect();
+        let rows = axis_positions(&transposed, &heights, boundary_height)?;
+
+        let layout: Vec<Room> = cells
+            .iter()
+            .zip(columns.iter().zip(rows.iter()))
+            .map(|(cell, ((left, right), (top, bottom)))| Room {
+                id: rooms[cell.room].id.clone(),
+                x: *left,
+                y: *top,
+                width: right - left,
+                height: bottom - top,
+            })
+            .collect();
+        let large_enough = layout.iter().zip(cells.iter()).all(|(room, cell)| {
+            let room_req = &rooms[cell.room];
+            let net = config.net_room(room, boundary_width, boundary_height);
+            net.width * net.height >= room_req.min_area - 1e-9
+                && room_req.min_width.is_none_or(|min_width| net.width >= min_width - 1e-9)
+                && room_req.min_depth.is_none_or(|min_depth| net.height >= min_depth - 1e-9)
+        });
+        if large_enough {
+            return Some(layout);
+        }
+
+        widths = cells
+            .iter()
+            .zip(rows.iter().zip(allowances.iter()))
+            .map(|(cell, ((top, bottom), (allowance_x, allowance_y)))| {
+                let room_req = &rooms[cell.room];
+                let net_height = bottom - top - allowance_y;
+                if net_height <= 0.0 {
+                    return f64::INFINITY;
+                }
+                needed((room_req.min_area / net_height).max(room_req.min_width.unwrap_or(0.0)), *allowance_x)
+            })
+            .collect();
+    }
+    None
+}
+
+/// Gross width a cell's left and right walls take up.
+fn wall_allowance(cell: &Cell, config: &SolverConfig) -> f64 {
+    let Some(walls) = config.walls else { return 0.0 };
+    let side = |on_boundary: bool| if on_boundary { walls.exterior } else { walls.interior / 2.0 };
+    side(cell.left == 0.0) + side(cell.right == 1.0)
+}
+
+fn round_up_to_grid(value: f64) -> f64 {
+    (value / GRID_SIZE - 1e-9).ceil() * GRID_SIZE
+}
+
+fn round_down_to_grid(value: f64) -> f64 {
+    (value / GRID_SIZE + 1e-9).floor() * GRID_SIZE
+}
+
+/// Horizontal positions for the cells, spanning exactly `length`.
+///
+/// Room edges on the same wall share a coordinate. Each cell must be at
+/// least its entry in `widths` wide, and cells sharing a horizontal wall
+/// must keep at least a grid step of it, which also keeps the floorplan's
+/// topology. Each coordinate is placed as far along the boundary as it is
+/// along the longest chain of bounds through it, so slack is shared evenly,
+/// then snapped to the grid and pushed right where a bound needs it. Bounds
+/// are whole grid steps and never push a coordinate past its latest
+/// position, so the result always fits. Returns `None` when the widths do
+/// not fit.
+fn axis_positions(cells: &[Cell], widths: &[f64], length: f64) -> Option<Vec<(f64, f64)>> {
+    // Variables 2i and 2i + 1 are cell i's left and right edges; the last two are the boundary sides
+    let (start, end) = (2 * cells.len(), 2 * cells.len() + 1);
+    let mut parent: Vec<usize> = (0..end + 1).collect();
+    fn find(parent: &mut [usize], var: usize) -> usize {
+        let mut root = var;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[var] = root;
+        root
+    }
+    fn union(parent: &mut [usize], a: usize, b: usize) {
+        let (a, b) = (find(parent, a), find(parent, b));
+        parent[a] = b;
+    }
+
+    let mut bounds: Vec<(usize, usize, f64)> = Vec::new();
+    for (i, cell) in cells.iter().enumerate() {
+        if cell.left == 0.0 {
+            union(&mut parent, 2 * i, start);
+        }
+        if cell.right == 1.0 {
+            union(&mut parent, 2 * i + 1, end);
+        }
+        bounds.push((2 * i, 2 * i + 1, widths[i]));
+
+        for (j, other) in cells.iter().enumerate() {
+            let rows_overlap = cell.top.max(other.top) < cell.bottom.min(other.bottom);
+            let columns_overlap = cell.left.max(other.left) < cell.right.min(other.right);
+            if cell.right == other.left && rows_overlap {
+                union(&mut parent, 2 * i + 1, 2 * j);
+            }
+            if cell.bottom == other.top && columns_overlap {
+                bounds.push((2 * i, 2 * j + 1, GRID_SIZE));
+                bounds.push((2 * j, 2 * i + 1, GRID_SIZE));
+            }
+        }
+    }
+
+    // Bounds run from smaller to larger unit coordinates, so that order is topological
+    let coordinate = |var: usize| match var {
+        _ if var == start => 0.0,
+        _ if var == end => 1.0,
+        _ if var.is_multiple_of(2) => cells[var / 2].left,
+        _ => cells[var / 2].right,
+    };
+    let mut bounds: Vec<(usize, usize, f64, f64, f64)> = bounds
+        .into_iter()
+        .map(|(from, to, gap)| (find(&mut parent, from), find(&mut parent, to), gap, coordinate(from), coordinate(to)))
+        .collect();
+
+    let mut earliest = vec![0.0; end + 1];
+    bounds.sort_by(|a, b| a.3.total_cmp(&b.3));
+    for &(from, to, gap, _, _) in bounds.iter() {
+        earliest[to] = f64::max(earliest[to], earliest[from] + gap);
+    }
+    let (start_root, end_root) = (find(&mut parent, start), find(&mut parent, end));
+    if earliest[end_root] > length + 1e-9 {
+        return None;
+    }
+
+    let mut remaining = vec![0.0; end + 1];
+    bounds.sort_by(|a, b| b.4.total_cmp(&a.4));
+    for &(from, to, gap, _, _) in bounds.iter() {
+        remaining[from] = f64::max(remaining[from], remaining[to] + gap);
+    }
+
+    // Spread each coordinate along its longest path, then push it right of its bounds on the grid
+    let mut position: Vec<f64> = (0..end + 1)
+        .map(|var| {
+            let path = earliest[var] + remaining[var];
+            if path > 0.0 { round_down_to_grid(length * earliest[var] / path) } else { 0.0 }
+        })
+        .collect();
+    position[start_root] = 0.0;
+    bounds.sort_by(|a, b| a.3.total_cmp(&b.3));
+    for &(from, to, gap, _, _) in bounds.iter() {
+        position[to] = f64::max(position[to], position[from] + gap);
+    }
+    position[end_root] = length;
+
+    Some(
+        (0..cells.len())
+            .map(|i| (position[find(&mut parent, 2 * i)], position[find(&mut parent, 2 * i + 1)]))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Rectangle;
+
+    fn room(id: &str, min_area: f64, adjacent_to: &[&str]) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn accept_all(_: &[Room]) -> LayoutScore {
+        LayoutScore { total_score: 0.0, violations: 0 }
+    }
+
+    // Test 1: test_pinwheel_dual_realises_wheel_graph
+    #[test]
+    fn test_pinwheel_dual_realises_wheel_graph() {
+        // A hall touching four rooms that each touch the next: only a pinwheel realises it
+        let rooms = vec![
+            room("hall", 6.0, &["a", "b", "c", "d"]),
+            room("a", 8.0, &["b"]),
+            room("b", 8.0, &["c"]),
+            room("c", 8.0, &["d"]),
+            room("d", 8.0, &["a"]),
+        ];
+
+        let layout = solve_rectangular_dual(&rooms, 8.0, 8.0, &SolverConfig::default(), &accept_all).unwrap();
+
+        let rect = |id: &str| Rectangle::from_room(layout.iter().find(|r| r.id == id).unwrap());
+        for room_req in rooms.iter() {
+            assert!(rect(&room_req.id).width * rect(&room_req.id).height >= room_req.min_area);
+            for other in room_req.adjacent_to.iter() {
+                assert!(rect(&room_req.id).shared_edge_length(&rect(other)) >= GRID_SIZE);
+            }
+        }
+        assert!(!rect("a").is_adjacent_to(&rect("c")) && !rect("b").is_adjacent_to(&rect("d")));
+
+        // The rooms tile the boundary
+        let total: f64 = layout.iter().map(|r| r.width * r.height).sum();
+        assert!((total - 64.0).abs() < 1e-9);
+        assert!(layout.iter().all(|r| Rectangle::from_room(r).is_within_boundary(8.0, 8.0)));
+    }
+
+    // Test 2: test_rectangular_dual_errors
+    #[test]
+    fn test_rectangular_dual_errors() {
+        let config = SolverConfig::default();
+
+        // Five mutually adjacent rooms form K5
+        let ids = ["a", "b", "c", "d", "e"];
+        let complete: Vec<RoomRequirement> = ids
+            .iter()
+            .map(|id| room(id, 4.0, &ids.iter().copied().filter(|other| other != id).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            solve_rectangular_dual(&complete, 10.0, 10.0, &config, &accept_all).err(),
+            Some(RectangularDualError::NonPlanar)
+        );
+
+        // A planar triangle with a forbidden pair among its edges cannot be realised
+        let mut triangle = vec![room("a", 4.0, &["b", "c"]), room("b", 4.0, &["c"]), room("c", 4.0, &[])];
+        triangle[2].relations.push(crate::types::AdjacencyRelation {
+            room_id: "a".to_string(),
+            strength: AdjacencyStrength::Forbidden,
+            weight: 0.0,
+        });
+        assert_eq!(
+            solve_rectangular_dual(&triangle, 10.0, 10.0, &config, &accept_all).err(),
+            Some(RectangularDualError::NoRectangularDual)
+        );
+
+        // A floorplan exists but the rooms need more area than the boundary has
+        let crowded = vec![room("a", 30.0, &["b"]), room("b", 30.0, &[])];
+        assert!(matches!(
+            solve_rectangular_dual(&crowded, 6.0, 6.0, &config, &accept_all),
+            Err(RectangularDualError::NotScalable { .. })
+        ));
+    }
+
+    // Test 3: test_preferred_relations_do_not_constrain_dual
+    #[test]
+    fn test_preferred_relations_do_not_constrain_dual() {
+        // Preferred relations forming K5 would be non-planar if they were edges of the dual
+        let ids = ["a", "b", "c", "d", "e"];
+        let rooms: Vec<RoomRequirement> = ids
+            .iter()
+            .map(|id| {
+                let mut room_req = room(id, 4.0, &[]);
+                room_req.relations = ids
+                    .iter()
+                    .filter(|other| *other != id)
+                    .map(|other| crate::types::AdjacencyRelation {
+                        room_id: other.to_string(),
+                        strength: AdjacencyStrength::Preferred,
+                        weight: 1.0,
+                    })
+                    .collect();
+                room_req
+            })
+            .collect();
+
+        let layout = solve_rectangular_dual(&rooms, 10.0, 10.0, &SolverConfig::default(), &accept_all).unwrap();
+
+        assert_eq!(layout.len(), 5);
+        let total: f64 = layout.iter().map(|r| r.width * r.height).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+    }
+}
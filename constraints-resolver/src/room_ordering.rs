@@ -1,4 +1,4 @@
-use crate::types::RoomRequirement;
+use crate::types::{AdjacencyStrength, RoomRequirement};
 
 pub fn order_rooms_by_constraints(mut rooms_reqs: Vec<RoomRequirement>) -> Vec<RoomRequirement> {
     rooms_reqs.sort_by(|a,b| {
@@ -11,9 +11,11 @@ pub fn order_rooms_by_constraints(mut rooms_reqs: Vec<RoomRequirement>) -> Vec<R
 
 fn count_constraints(room_req: &RoomRequirement) -> usize {
     let mut count = 0;
-    count += room_req.adjacent_to.len();
-    count += room_req.not_adjacent_to.len();
-    count += if room_req.has_exterior_wall { 1 } else { 0 };
+    count += room_req.related_rooms(AdjacencyStrength::Required).len();
+    count += room_req.related_rooms(AdjacencyStrength::Forbidden).len();
+    count += room_req.distance_constraints.iter().filter(|c| c.hard).count();
+    count += room_req.position_constraints.iter().filter(|c| c.hard).count();
+    count += if room_req.needs_exterior_wall() { 1 } else { 0 };
     count
 }
 
@@ -30,6 +32,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string(), "room3".to_string()],
             not_adjacent_to: vec!["room4".to_string()],
             has_exterior_wall: true,
+            ..Default::default()
         };
         
         let count = count_constraints(&room);
@@ -45,6 +48,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let count = count_constraints(&room);
@@ -60,6 +64,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string(), "room3".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let count = count_constraints(&room);
@@ -76,6 +81,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string(), "room3".to_string()],
             not_adjacent_to: vec!["room4".to_string()],
             has_exterior_wall: true,
+            ..Default::default()
         };
         
         // room2: 1 constraint (adjacent_to = 1)
@@ -85,6 +91,7 @@ mod tests {
             adjacent_to: vec!["room1".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // room3: 2 constraints (adjacent_to = 2)
@@ -94,6 +101,7 @@ mod tests {
             adjacent_to: vec!["room1".to_string(), "room2".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let rooms = vec![room1, room2, room3];
@@ -114,6 +122,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string(), "room3".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let room2 = RoomRequirement {
@@ -122,6 +131,7 @@ mod tests {
             adjacent_to: vec!["room1".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: true,
+            ..Default::default()
         };
         
         let rooms = vec![room1, room2];
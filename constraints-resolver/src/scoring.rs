@@ -1,5 +1,66 @@
-use crate::types::{Room, RoomRequirement};
+use serde::{Deserialize, Serialize};
+
+use crate::config::SolverConfig;
+use crate::distance::{is_ruled_out, measure_distance, walking_route};
+use crate::types::{AdjacencyStrength, DistanceMetric, Orientation, Room, RoomRequirement, Zone};
 use crate::geometry::Rectangle;
+use crate::violation::{Dimension, Violation};
+
+/// Point values for the built-in scoring terms.
+///
+/// Missing fields deserialize to their defaults, so JavaScript callers can
+/// override a single weight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringWeights {
+    /// Points for a placement with no hard constraint violations.
+    pub hard_constraints: f64,
+    /// Extra points added on top of everything else when there are no violations.
+    pub validity_bonus: f64,
+    /// Multiplier on every adjacency relation weight (a required adjacency is worth 5 by default).
+    pub adjacency_scale: f64,
+    /// Points for touching the exterior wall, whether or not it is required.
+    pub exterior_wall_bonus: f64,
+    /// Upper limit on the soft preference score.
+    pub soft_preference_cap: f64,
+    /// Points for a room exactly at its minimum area, scaled down as it grows.
+    pub space_efficiency: f64,
+    /// Points for a public room at the entrance or a private room at the far end of the plan.
+    pub zone_gradient: f64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            hard_constraints: 20.0,
+            validity_bonus: 5.0,
+            adjacency_scale: 1.0,
+            exterior_wall_bonus: 3.0,
+            soft_preference_cap: 15.0,
+            space_efficiency: 10.0,
+            zone_gradient: 3.0,
+        }
+    }
+}
+
+/// A custom scoring term combined with the built-in ones.
+///
+/// Objectives are only evaluated for placements without hard constraint
+/// violations; their results are summed into `objective_score`.
+pub trait Objective {
+    /// Short name used to identify the term.
+    fn name(&self) -> &str;
+
+    /// Score a candidate placement. Higher is better; negative values penalise.
+    fn score(
+        &self,
+        placed_room: &Room,
+        room_req: &RoomRequirement,
+        already_placed: &[Room],
+        boundary_width: f64,
+        boundary_height: f64,
+    ) -> f64;
+}
 
 #[derive(Debug, Clone)]
 pub struct PositionScore {
@@ -7,8 +68,26 @@ pub struct PositionScore {
     pub hard_constraint_score: f64,
     pub soft_preference_score: f64,
     pub space_efficiency_score: f64,
+    pub objective_score: f64,
     pub has_violations: bool,
-    pub violation_reasons: Vec<String>,
+    pub violations: Vec<Violation>,
+}
+
+/// Score of a complete layout, as the sum of its rooms' `score_position`
+/// totals, and how many hard constraints it breaks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutScore {
+    pub total_score: f64,
+    /// Rooms with a hard constraint violation, plus one for each broken
+    /// layout-wide constraint (zone contiguity, reachability).
+    pub violations: usize,
+}
+
+impl LayoutScore {
+    /// Whether the layout meets every hard constraint.
+    pub fn is_valid(&self) -> bool {
+        self.violations == 0
+    }
 }
 
 pub fn score_position(
@@ -17,41 +96,50 @@ pub fn score_position(
     already_placed: &[Room],
     boundary_width: f64,
     boundary_height: f64,
+    config: &SolverConfig,
 ) -> PositionScore {
-    let (is_valid, violations) = check_hard_constraints(placed_room, room_req, already_placed, boundary_width, boundary_height);
+    let weights = &config.weights;
+    let (is_valid, violations) = check_hard_constraints(placed_room, room_req, already_placed, boundary_width, boundary_height, config);
     let has_violations = !is_valid;
-    let hard_score = calculate_hard_constraint_score(has_violations);
-    let mut soft_score = calculate_soft_preference_score(placed_room, room_req, already_placed, boundary_width, boundary_height);
-    let mut efficiency_score = calculate_space_efficiency_score(placed_room, room_req);
-    
+    let hard_score = calculate_hard_constraint_score(has_violations, weights);
+    let mut soft_score = calculate_soft_preference_score(placed_room, room_req, already_placed, boundary_width, boundary_height, config);
+    let net_room = config.net_room(placed_room, boundary_width, boundary_height);
+    let mut efficiency_score = calculate_space_efficiency_score(&net_room, room_req, weights);
+    let mut objective_score = 0.0;
+
     // If there are violations, zero out soft scores
     if has_violations {
         soft_score = 0.0;
         efficiency_score = 0.0;
+    } else {
+        for objective in config.objectives.iter() {
+            objective_score += objective.score(placed_room, room_req, already_placed, boundary_width, boundary_height);
+        }
     }
 
-    let mut total_score = hard_score + soft_score + efficiency_score;
+    let mut total_score = hard_score + soft_score + efficiency_score + objective_score;
 
     if !has_violations {
-        total_score += 5.0;
+        total_score += weights.validity_bonus;
     }
 
     PositionScore {
-        total_score: total_score,
+        total_score,
         hard_constraint_score: hard_score,
         soft_preference_score: soft_score,
         space_efficiency_score: efficiency_score,
-        has_violations: has_violations,
-        violation_reasons: violations,
+        objective_score,
+        has_violations,
+        violations,
     }
 }
 
-fn calculate_space_efficiency_score(placed_room: &Room, room_req: &RoomRequirement) -> f64 {
+fn calculate_space_efficiency_score(placed_room: &Room, room_req: &RoomRequirement, weights: &ScoringWeights) -> f64 {
     let actual_area = placed_room.width * placed_room.height;
     let min_area = room_req.min_area;
     let efficiency_ratio = min_area / actual_area;
-    let score = efficiency_ratio * 10.0;
-    score.min(10.0f64)
+    let score = efficiency_ratio * weights.space_efficiency;
+    score.min(weights.space_efficiency)
 }
 
 fn calculate_soft_preference_score(
@@ -60,36 +148,72 @@ fn calculate_soft_preference_score(
     already_placed: &[Room],
     boundary_width: f64,
     boundary_height: f64,
-) -> f64{
+    config: &SolverConfig,
+) -> f64 {
+    let weights = &config.weights;
     let mut score: f64 = 0.0;
     let room_rect = Rectangle::from_room(placed_room);
 
-    // +5 points for each satisfied adjacency preference
-    for adjacent in room_req.adjacent_to.iter() {
-        for placed in already_placed {
-            if placed.id == *adjacent {
-                let existing_rect = Rectangle::from_room(placed);
-                if room_rect.is_adjacent_to(&existing_rect) {
-                    score += 5.0;
-                    break;
-                }
+    // Weighted adjacency relations: reward wanted neighbours, penalise avoided ones
+    for relation in room_req.adjacency_relations() {
+        let Some(placed) = already_placed.iter().find(|r| r.id == relation.room_id) else {
+            continue;
+        };
+        if !room_rect.is_adjacent_to(&Rectangle::from_room(placed)) {
+            continue;
+        }
+
+        match relation.strength {
+            AdjacencyStrength::Required | AdjacencyStrength::StronglyPreferred | AdjacencyStrength::Preferred => {
+                score += relation.weight * weights.adjacency_scale;
+            }
+            AdjacencyStrength::Avoid => score -= relation.weight * weights.adjacency_scale,
+            AdjacencyStrength::Forbidden => {}
+        }
+    }
+
+    // Soft distance constraints that are met
+    for constraint in room_req.distance_constraints.iter().filter(|c| !c.hard) {
+        if let Some(other) = already_placed.iter().find(|r| r.id == constraint.room_id) {
+            let distance = measure_distance(constraint.metric, placed_room, other, already_placed);
+            if constraint.is_satisfied_by(distance) {
+                score += constraint.weight;
             }
         }
     }
 
-    // +3 points if touches external wall (bonus, even if not required)
+    // Soft relative positions that hold
+    for constraint in room_req.position_constraints.iter().filter(|c| !c.hard) {
+        if let Some(other) = already_placed.iter().find(|r| r.id == constraint.room_id)
+            && room_rect.is_positioned(constraint.position, &Rectangle::from_room(other))
+        {
+            score += constraint.weight;
+        }
+    }
+
+    // Soft alignment with group members already placed
+    for (group, other) in config.alignment_partners(&placed_room.id, already_placed) {
+        if !group.hard && room_rect.is_aligned_with(group.alignment, &Rectangle::from_room(other)) {
+            score += group.weight;
+        }
+    }
+
+    // Public rooms near the entrance, private rooms deep in the plan
+    score += zone_gradient_score(&room_rect, room_req, boundary_width, boundary_height, config);
+
+    // Bonus if touches external wall (even if not required)
     if room_rect.touches_exterior_wall(boundary_width, boundary_height) {
-        score += 3.0;
+        score += weights.exterior_wall_bonus;
     }
 
-    score.min(15.0f64)
+    score.min(weights.soft_preference_cap)
 }
 
-fn calculate_hard_constraint_score(has_violations: bool) -> f64 {
+fn calculate_hard_constraint_score(has_violations: bool, weights: &ScoringWeights) -> f64 {
     if has_violations {
         return 0.0;
     }
-    return 20.0;
+    weights.hard_constraints
 }
 
 fn check_hard_constraints(
@@ -98,31 +222,77 @@ fn check_hard_constraints(
     already_placed: &[Room],
     boundary_width: f64,
     boundary_height: f64,
-) -> (bool, Vec<String>) {
+    config: &SolverConfig,
+) -> (bool, Vec<Violation>) {
     let mut violations = Vec::new();
     let room_rect = Rectangle::from_room(placed_room);
 
     // Must be within boundary
     if !room_rect.is_within_boundary(boundary_width, boundary_height) {
-        violations.push("Room is outside the boundary".to_string());
+        violations.push(Violation::OutsideBoundary { room_id: placed_room.id.clone() });
     }
 
     // Must not overlap with already placed rooms
     for placed in already_placed {
-        if room_rect.overlaps_with(&Rectangle::from_room(placed)) {
-            violations.push(format!("Room overlaps with already placed room: {}", placed.id));
+        let placed_rect = Rectangle::from_room(placed);
+        if room_rect.overlaps_with(&placed_rect) {
+            violations.push(Violation::Overlap {
+                room_id: placed_room.id.clone(),
+                other_room_id: placed.id.clone(),
+                overlap_area: room_rect.overlap_area(&placed_rect),
+            });
+        }
+    }
+
+    // Must respect the size bounds, measured inside the walls
+    let net_room = config.net_room(placed_room, boundary_width, boundary_height);
+    violations.extend(check_size_bounds(&net_room, room_req));
+
+    // Must touch the exterior wall, on one of the required sides when given
+    if room_req.exterior_sides.is_empty() {
+        if room_req.has_exterior_wall && !room_rect.touches_exterior_wall(boundary_width, boundary_height) {
+            violations.push(Violation::MissingExteriorWall { room_id: placed_room.id.clone() });
         }
+    } else if !room_req
+        .exterior_sides
+        .iter()
+        .any(|side| room_rect.touches_side(*side, boundary_width, boundary_height))
+    {
+        violations.push(Violation::NotOnRequiredSide {
+            room_id: placed_room.id.clone(),
+            sides: room_req.exterior_sides.clone(),
+        });
     }
 
-    // Must touch the exterior wall
-    if room_req.has_exterior_wall {
-        if !room_rect.touches_exterior_wall(boundary_width, boundary_height) {
-            violations.push("Room touches the exterior wall".to_string());
+    // Must have enough facade facing the required directions
+    if room_req.has_facade_requirement() {
+        let facade_length = facade_length(
+            &room_rect,
+            &room_req.facade_orientations,
+            boundary_width,
+            boundary_height,
+            config.north_angle,
+        );
+        let min_facade_length = room_req.min_facade_length.unwrap_or(0.0);
+        if facade_length == 0.0 || facade_length < min_facade_length {
+            violations.push(Violation::InsufficientFacade {
+                room_id: placed_room.id.clone(),
+                orientations: room_req.facade_orientations.clone(),
+                facade_length,
+                min_facade_length,
+            });
         }
     }
 
+    // Must contain an entrance
+    if room_req.contains_entrance
+        && !config.entrances.iter().any(|entrance| room_rect.contains_point(entrance.x, entrance.y))
+    {
+        violations.push(Violation::MissingEntrance { room_id: placed_room.id.clone() });
+    }
+
     // Must be adjacent to required rooms (only check if those rooms are already placed)
-    for adjacent in room_req.adjacent_to.iter() {
+    for adjacent in room_req.related_rooms(AdjacencyStrength::Required).iter() {
         // Check if the required adjacent room has been placed
         let required_room_placed = already_placed.iter().find(|r| r.id == *adjacent);
         
@@ -130,7 +300,10 @@ fn check_hard_constraints(
             // Room has been placed, so check adjacency
             let existing_rect = Rectangle::from_room(required_room);
             if !room_rect.is_adjacent_to(&existing_rect) {
-                violations.push(format!("Room is not adjacent to required room: {}", adjacent));
+                violations.push(Violation::MissingAdjacency {
+                    room_id: placed_room.id.clone(),
+                    required_room_id: adjacent.clone(),
+                });
             }
         }
         // If the required room hasn't been placed yet, skip this check
@@ -139,24 +312,257 @@ fn check_hard_constraints(
 
 
     // Cannot be adjacent to forbidden rooms
-    for forbidden in room_req.not_adjacent_to.iter() {
+    for forbidden in room_req.related_rooms(AdjacencyStrength::Forbidden).iter() {
         for placed in already_placed {
             if placed.id == *forbidden {
                 let existing_rect = Rectangle::from_room(placed);
                 if room_rect.is_adjacent_to(&existing_rect) {
-                    violations.push(format!("Room is adjacent to forbidden room: {}", forbidden));
+                    violations.push(Violation::ForbiddenAdjacency {
+                        room_id: placed_room.id.clone(),
+                        forbidden_room_id: forbidden.clone(),
+                        shared_edge_length: room_rect.shared_edge_length(&existing_rect),
+                    });
                     break;
                 }
             }
         }
     }
 
+    // Must respect hard distance constraints to rooms already placed, as far as they are settled
+    for constraint in room_req.distance_constraints.iter().filter(|c| c.hard) {
+        if let Some(other) = already_placed.iter().find(|r| r.id == constraint.room_id)
+            && is_ruled_out(constraint, placed_room, other, already_placed)
+        {
+            let distance = measure_distance(constraint.metric, placed_room, other, already_placed);
+            violations.push(Violation::DistanceOutOfRange {
+                room_id: placed_room.id.clone(),
+                other_room_id: constraint.room_id.clone(),
+                metric: constraint.metric,
+                distance,
+                min_distance: constraint.min_distance,
+                max_distance: constraint.max_distance,
+            });
+        }
+    }
+
+    // Must sit on the required side of rooms already placed
+    for constraint in room_req.position_constraints.iter().filter(|c| c.hard) {
+        if let Some(other) = already_placed.iter().find(|r| r.id == constraint.room_id)
+            && !room_rect.is_positioned(constraint.position, &Rectangle::from_room(other))
+        {
+            violations.push(Violation::RelativePositionViolated {
+                room_id: placed_room.id.clone(),
+                other_room_id: constraint.room_id.clone(),
+                position: constraint.position,
+            });
+        }
+    }
+
+    // Must line up with members of hard alignment groups already placed
+    for (group, other) in config.alignment_partners(&placed_room.id, already_placed) {
+        if group.hard && !room_rect.is_aligned_with(group.alignment, &Rectangle::from_room(other)) {
+            violations.push(Violation::Misaligned {
+                room_id: placed_room.id.clone(),
+                other_room_id: other.id.clone(),
+                alignment: group.alignment,
+            });
+        }
+    }
+
     (violations.is_empty(), violations)
 }
 
+/// Check the hard adjacency, distance and position constraints that rooms
+/// already placed hold against a new room.
+///
+/// `score_position` checks a room's own constraints against the rooms
+/// placed before it, so a constraint whose owner is placed first would never
+/// be checked; this checks it when its target is placed instead. Violations
+/// are reported for the owning room.
+pub fn check_incoming_constraints(
+    placed_room: &Room,
+    already_placed: &[Room],
+    room_requirements: &[RoomRequirement],
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for owner in already_placed {
+        let Some(owner_req) = room_requirements.iter().find(|r| r.id == owner.id) else { continue };
+        let owner_rect = Rectangle::from_room(owner);
+        let placed_rect = Rectangle::from_room(placed_room);
+
+        if owner_req.related_rooms(AdjacencyStrength::Required).contains(&placed_room.id)
+            && !owner_rect.is_adjacent_to(&placed_rect)
+        {
+            violations.push(Violation::MissingAdjacency {
+                room_id: owner.id.clone(),
+                required_room_id: placed_room.id.clone(),
+            });
+        }
+        if owner_req.related_rooms(AdjacencyStrength::Forbidden).contains(&placed_room.id)
+            && owner_rect.is_adjacent_to(&placed_rect)
+        {
+            violations.push(Violation::ForbiddenAdjacency {
+                room_id: owner.id.clone(),
+                forbidden_room_id: placed_room.id.clone(),
+                shared_edge_length: owner_rect.shared_edge_length(&placed_rect),
+            });
+        }
+
+        let distance_constraints =
+            owner_req.distance_constraints.iter().filter(|c| c.hard && c.room_id == placed_room.id);
+        for constraint in distance_constraints {
+            if is_ruled_out(constraint, owner, placed_room, already_placed) {
+                let distance = measure_distance(constraint.metric, owner, placed_room, already_placed);
+                violations.push(Violation::DistanceOutOfRange {
+                    room_id: owner.id.clone(),
+                    other_room_id: placed_room.id.clone(),
+                    metric: constraint.metric,
+                    distance,
+                    min_distance: constraint.min_distance,
+                    max_distance: constraint.max_distance,
+                });
+            }
+        }
+
+        let position_constraints =
+            owner_req.position_constraints.iter().filter(|c| c.hard && c.room_id == placed_room.id);
+        for constraint in position_constraints {
+            if !owner_rect.is_positioned(constraint.position, &placed_rect) {
+                violations.push(Violation::RelativePositionViolated {
+                    room_id: owner.id.clone(),
+                    other_room_id: placed_room.id.clone(),
+                    position: constraint.position,
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Check every hard distance constraint between the rooms of a complete layout.
+///
+/// The per-room checks only reject distances that no later room could fix,
+/// leaving walking distances to be settled here; two rooms with no walk
+/// between them are infinitely far apart.
+pub fn check_distance_constraints(rooms: &[Room], room_requirements: &[RoomRequirement]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for room in rooms {
+        let Some(room_req) = room_requirements.iter().find(|r| r.id == room.id) else { continue };
+        for constraint in room_req.distance_constraints.iter().filter(|c| c.hard) {
+            let Some(other) = rooms.iter().find(|r| r.id == constraint.room_id) else { continue };
+            let distance = match constraint.metric {
+                DistanceMetric::WalkingPath => walking_route(room, other, rooms).unwrap_or(f64::INFINITY),
+                metric => measure_distance(metric, room, other, rooms),
+            };
+            if !constraint.is_satisfied_by(distance) {
+                violations.push(Violation::DistanceOutOfRange {
+                    room_id: room.id.clone(),
+                    other_room_id: other.id.clone(),
+                    metric: constraint.metric,
+                    distance,
+                    min_distance: constraint.min_distance,
+                    max_distance: constraint.max_distance,
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Gradient score for a zoned room, from its centre's distance to the nearest entrance.
+///
+/// Depth is that distance over the boundary diagonal, so 0.0 is at the
+/// entrance and 1.0 the far corner. Service rooms and plans without an
+/// entrance score nothing.
+fn zone_gradient_score(
+    room_rect: &Rectangle,
+    room_req: &RoomRequirement,
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> f64 {
+    let (center_x, center_y) = room_rect.center();
+    let nearest = config
+        .entrances
+        .iter()
+        .map(|entrance| (entrance.x - center_x).hypot(entrance.y - center_y))
+        .fold(f64::INFINITY, f64::min);
+    if !nearest.is_finite() {
+        return 0.0;
+    }
+    let depth = (nearest / boundary_width.hypot(boundary_height)).min(1.0);
+
+    match room_req.zone {
+        Some(Zone::Public) => config.weights.zone_gradient * (1.0 - depth),
+        Some(Zone::Private) => config.weights.zone_gradient * depth,
+        Some(Zone::Service) | None => 0.0,
+    }
+}
+
+/// Exterior wall length facing any of the orientations (any direction when empty).
+fn facade_length(
+    room_rect: &Rectangle,
+    orientations: &[Orientation],
+    boundary_width: f64,
+    boundary_height: f64,
+    north_angle: f64,
+) -> f64 {
+    room_rect
+        .exterior_contacts(boundary_width, boundary_height)
+        .into_iter()
+        .filter(|(side, _)| orientations.is_empty() || orientations.contains(&side.orientation(north_angle)))
+        .map(|(_, length)| length)
+        .sum()
+}
+
+fn check_size_bounds(placed_room: &Room, room_req: &RoomRequirement) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let room_id = &placed_room.id;
+    let area = placed_room.width * placed_room.height;
+
+    if area < room_req.min_area {
+        violations.push(Violation::AreaTooSmall { room_id: room_id.clone(), area, min_area: room_req.min_area });
+    }
+
+    if let Some(max_area) = room_req.max_area
+        && area > max_area
+    {
+        violations.push(Violation::AreaTooLarge { room_id: room_id.clone(), area, max_area });
+    }
+
+    let dimensions = [
+        (Dimension::Width, placed_room.width, room_req.min_width),
+        (Dimension::Depth, placed_room.height, room_req.min_depth),
+    ];
+    for (dimension, actual, minimum) in dimensions {
+        if let Some(minimum) = minimum
+            && actual < minimum
+        {
+            violations.push(Violation::DimensionTooSmall { room_id: room_id.clone(), dimension, actual, minimum });
+        }
+    }
+
+    let aspect_ratio = placed_room.width.max(placed_room.height) / placed_room.width.min(placed_room.height);
+    let too_square = room_req.min_aspect.is_some_and(|min_aspect| aspect_ratio < min_aspect);
+    let too_elongated = room_req.max_aspect.is_some_and(|max_aspect| aspect_ratio > max_aspect);
+    if too_square || too_elongated {
+        violations.push(Violation::AspectRatioOutOfRange {
+            room_id: room_id.clone(),
+            aspect_ratio,
+            min_aspect: room_req.min_aspect,
+            max_aspect: room_req.max_aspect,
+        });
+    }
+
+    violations
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{
+        AdjacencyRelation, Alignment, AlignmentGroup, BoundarySide, Entrance, DistanceConstraint, DistanceMetric, PositionConstraint, RelativePosition,
+    };
 
     #[test]
     // Test 1: test_no_violations_when_valid_position
@@ -177,6 +583,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let already_placed = vec![];
@@ -187,6 +594,7 @@ mod tests {
             &already_placed,
             10.0,  // boundary width
             10.0,  // boundary height
+            &SolverConfig::default(),
         );
         
         assert!(is_valid);
@@ -211,6 +619,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         let already_placed = vec![];
@@ -221,10 +630,11 @@ mod tests {
             &already_placed,
             10.0,  // boundary width
             10.0,  // boundary height
+            &SolverConfig::default(),
         );
         
         assert!(!is_valid);
-        assert!(violations.iter().any(|v| v.contains("boundary")));
+        assert!(violations.iter().any(|v| matches!(v, Violation::OutsideBoundary { .. })));
     }
 
     // Test 3: test_violation_when_overlapping
@@ -245,6 +655,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (4.0, 4.0) size 4x4 (these overlap!)
@@ -264,10 +675,14 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert!(!is_valid);
-        assert!(violations.iter().any(|v| v.contains("overlap")));
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::Overlap { other_room_id, overlap_area, .. } if other_room_id == "room2" && *overlap_area == 4.0
+        )));
     }
 
     // Test 4: test_violation_when_missing_required_adjacency
@@ -289,6 +704,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (5.0, 5.0) size 3x3 (not adjacent!)
@@ -308,10 +724,14 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert!(!is_valid);
-        assert!(violations.iter().any(|v| v.contains("room2")));
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::MissingAdjacency { required_room_id, .. } if required_room_id == "room2"
+        )));
     }
 
     // Test 5: test_violation_when_adjacent_to_forbidden_room
@@ -333,6 +753,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec!["room2".to_string()],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (3.0, 0.0) size 3x3 (they ARE adjacent!)
@@ -352,23 +773,28 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert!(!is_valid);
-        assert!(violations.iter().any(|v| v.contains("forbidden")));
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::ForbiddenAdjacency { forbidden_room_id, shared_edge_length, .. }
+                if forbidden_room_id == "room2" && *shared_edge_length == 3.0
+        )));
     }
 
     // Test 6: test_hard_constraint_score_zero_when_violations
     #[test]
     fn test_hard_constraint_score_zero_when_violations() {
-        let score = calculate_hard_constraint_score(true);
+        let score = calculate_hard_constraint_score(true, &ScoringWeights::default());
         assert_eq!(score, 0.0);
     }
 
     // Test 7: test_hard_constraint_score_full_when_no_violations
     #[test]
     fn test_hard_constraint_score_full_when_no_violations() {
-        let score = calculate_hard_constraint_score(false);
+        let score = calculate_hard_constraint_score(false, &ScoringWeights::default());
         assert_eq!(score, 20.0);
     }
 
@@ -391,6 +817,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (3.0, 0.0) size 3x3 (adjacent!)
@@ -410,6 +837,7 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert!(score >= 5.0, "Expected score >= 5.0, got {}", score);
@@ -434,6 +862,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: empty
@@ -445,6 +874,7 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert_eq!(score, 3.0, "Expected score 3.0 for external wall bonus, got {}", score);
@@ -469,6 +899,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string(), "room3".to_string(), "room4".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (3.0, 0.0), room3 at (0.0, 3.0), room4 at (3.0, 3.0) (all adjacent)
@@ -495,6 +926,7 @@ mod tests {
             &already_placed,
             10.0,
             10.0,
+            &SolverConfig::default(),
         );
         
         assert!(score <= 15.0, "Expected score <= 15.0 (capped), got {}", score);
@@ -517,9 +949,10 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
-        let score = calculate_space_efficiency_score(&placed_room, &room_req);
+        let score = calculate_space_efficiency_score(&placed_room, &room_req, &ScoringWeights::default());
         assert_eq!(score, 10.0);  // 20/20 * 10 = 10.0
     }
 
@@ -542,10 +975,11 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Score should be: 20.0 / 25.0 * 10.0 = 8.0
-        let score = calculate_space_efficiency_score(&placed_room, &room_req);
+        let score = calculate_space_efficiency_score(&placed_room, &room_req, &ScoringWeights::default());
         assert_eq!(score, 8.0, "Expected score 8.0, got {}", score);
     }
 
@@ -568,13 +1002,14 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: true,
+            ..Default::default()
         };
         
         // Already placed: empty
         let already_placed = vec![];
         
         // Boundary: 10x10
-        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0);
+        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
         
         // Expected scores:
         // Hard: 20.0 (no violations)
@@ -584,7 +1019,7 @@ mod tests {
         // Total: 38.0
         assert_eq!(score.total_score, 38.0, "Expected total score 38.0, got {}", score.total_score);
         assert!(!score.has_violations);
-        assert!(score.violation_reasons.is_empty());
+        assert!(score.violations.is_empty());
     }
 
     // Test 14: test_score_position_zero_when_violations
@@ -606,13 +1041,14 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: empty
         let already_placed = vec![];
         
         // Boundary: 10x10
-        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0);
+        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
         
         // Expected:
         // Hard: 0.0 (violations)
@@ -621,7 +1057,7 @@ mod tests {
         // Total: 0.0
         assert_eq!(score.total_score, 0.0, "Expected total score 0.0, got {}", score.total_score);
         assert!(score.has_violations);
-        assert!(score.violation_reasons.len() > 0);
+        assert!(!score.violations.is_empty());
     }
 
     // Test 15: test_score_position_with_adjacency_bonus
@@ -643,6 +1079,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: room2 at (3.0, 0.0) size 3.0 x 3.0 (adjacent!)
@@ -657,7 +1094,7 @@ mod tests {
         ];
         
         // Boundary: 10x10
-        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0);
+        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
         
         // Expected:
         // Hard: 20.0
@@ -688,17 +1125,538 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
         
         // Already placed: empty
         let already_placed = vec![];
         
         // Boundary: 10x10
-        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0);
+        let score = score_position(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
         
         // Expected efficiency: 20.0 / 25.0 * 10.0 = 8.0
         assert_eq!(score.space_efficiency_score, 8.0, "Expected efficiency score 8.0, got {}", score.space_efficiency_score);
         // Total: 20.0 (hard) + 0.0 (soft) + 8.0 (efficiency) + 5.0 (base) = 33.0
         assert_eq!(score.total_score, 33.0, "Expected total score 33.0, got {}", score.total_score);
     }
-}
\ No newline at end of file
+
+    // Test 17: test_violation_when_missing_exterior_wall
+    #[test]
+    fn test_violation_when_missing_exterior_wall() {
+        // Room at (2.0, 2.0) size 3x3 (interior, touches no wall)
+        let placed_room = Room {
+            id: "room1".to_string(),
+            x: 2.0,
+            y: 2.0,
+            width: 3.0,
+            height: 3.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 9.0,
+            adjacent_to: vec![],
+            not_adjacent_to: vec![],
+            has_exterior_wall: true,
+            ..Default::default()
+        };
+
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &[], 10.0, 10.0, &SolverConfig::default());
+
+        assert!(!is_valid);
+        assert_eq!(violations, vec![Violation::MissingExteriorWall { room_id: "room1".to_string() }]);
+    }
+
+    // Test 18: test_violations_when_size_bounds_broken
+    #[test]
+    fn test_violations_when_size_bounds_broken() {
+        // Corridor-like 1 x 20 room
+        let placed_room = Room {
+            id: "bedroom".to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 20.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 12.0,
+            max_area: Some(16.0),
+            min_width: Some(2.5),
+            max_aspect: Some(2.0),
+            ..Default::default()
+        };
+
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &[], 30.0, 30.0, &SolverConfig::default());
+
+        assert!(!is_valid);
+        assert!(violations.iter().any(|v| matches!(v, Violation::AreaTooLarge { max_area, .. } if *max_area == 16.0)));
+        assert!(violations.iter().any(|v| matches!(v, Violation::DimensionTooSmall { dimension: Dimension::Width, .. })));
+        assert!(violations.iter().any(|v| matches!(v, Violation::AspectRatioOutOfRange { aspect_ratio, .. } if *aspect_ratio == 20.0)));
+    }
+
+    // Test 19: test_soft_score_uses_relation_weights
+    #[test]
+    fn test_soft_score_uses_relation_weights() {
+        // Room at (2.0, 2.0) size 3x3 (interior, no wall bonus)
+        let placed_room = Room {
+            id: "living".to_string(),
+            x: 2.0,
+            y: 2.0,
+            width: 3.0,
+            height: 3.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "living".to_string(),
+            min_area: 9.0,
+            relations: vec![
+                AdjacencyRelation {
+                    room_id: "dining".to_string(),
+                    strength: AdjacencyStrength::Preferred,
+                    weight: 6.0,
+                },
+                AdjacencyRelation {
+                    room_id: "bathroom".to_string(),
+                    strength: AdjacencyStrength::Avoid,
+                    weight: 2.5,
+                },
+            ],
+            ..Default::default()
+        };
+
+        // dining to the right, bathroom below: both adjacent
+        let already_placed = vec![
+            Room {
+                id: "dining".to_string(),
+                x: 5.0,
+                y: 2.0,
+                width: 3.0,
+                height: 3.0,
+            },
+            Room {
+                id: "bathroom".to_string(),
+                x: 2.0,
+                y: 5.0,
+                width: 2.0,
+                height: 2.0,
+            },
+        ];
+
+        let score = calculate_soft_preference_score(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+
+        assert_eq!(score, 3.5, "Expected 6.0 - 2.5 = 3.5, got {}", score);
+    }
+
+    // Test 20: test_hard_constraints_only_enforce_required_and_forbidden
+    #[test]
+    fn test_hard_constraints_only_enforce_required_and_forbidden() {
+        let placed_room = Room {
+            id: "living".to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 3.0,
+            height: 3.0,
+        };
+
+        let relation = |room_id: &str, strength| AdjacencyRelation {
+            room_id: room_id.to_string(),
+            strength,
+            weight: strength.default_weight(),
+        };
+        let room_req = RoomRequirement {
+            id: "living".to_string(),
+            min_area: 9.0,
+            relations: vec![
+                relation("study", AdjacencyStrength::StronglyPreferred),
+                relation("bathroom", AdjacencyStrength::Avoid),
+                relation("kitchen", AdjacencyStrength::Required),
+            ],
+            ..Default::default()
+        };
+
+        // study is far away, bathroom touches living, kitchen does not
+        let already_placed = vec![
+            Room { id: "study".to_string(), x: 7.0, y: 7.0, width: 3.0, height: 3.0 },
+            Room { id: "bathroom".to_string(), x: 3.0, y: 0.0, width: 2.0, height: 2.0 },
+            Room { id: "kitchen".to_string(), x: 7.0, y: 0.0, width: 3.0, height: 3.0 },
+        ];
+
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+
+        // Missing preferred study and adjacent avoided bathroom are not violations
+        assert!(!is_valid);
+        assert_eq!(
+            violations,
+            vec![Violation::MissingAdjacency {
+                room_id: "living".to_string(),
+                required_room_id: "kitchen".to_string(),
+            }]
+        );
+    }
+
+    // Test 21: test_score_position_adds_objectives_only_when_valid
+    #[test]
+    fn test_score_position_adds_objectives_only_when_valid() {
+        struct Flat;
+
+        impl Objective for Flat {
+            fn name(&self) -> &str {
+                "flat"
+            }
+
+            fn score(&self, _: &Room, _: &RoomRequirement, _: &[Room], _: f64, _: f64) -> f64 {
+                7.0
+            }
+        }
+
+        let room_req = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 20.0,
+            ..Default::default()
+        };
+        let config = SolverConfig {
+            weights: ScoringWeights { exterior_wall_bonus: 1.0, ..Default::default() },
+            ..Default::default()
+        }
+        .with_objective(Flat);
+
+        // Valid: 20 hard + 1 wall + 10 efficiency + 7 objective + 5 bonus
+        let inside = Room { id: "room1".to_string(), x: 0.0, y: 0.0, width: 4.0, height: 5.0 };
+        let score = score_position(&inside, &room_req, &[], 10.0, 10.0, &config);
+        assert_eq!(score.objective_score, 7.0);
+        assert_eq!(score.total_score, 43.0);
+
+        // Outside the boundary: objective is skipped
+        let outside = Room { id: "room1".to_string(), x: 8.0, y: 8.0, width: 4.0, height: 5.0 };
+        let score = score_position(&outside, &room_req, &[], 10.0, 10.0, &config);
+        assert_eq!(score.objective_score, 0.0);
+        assert_eq!(score.total_score, 0.0);
+    }
+
+    // Test 22: test_hard_distance_constraint_violation
+    #[test]
+    fn test_hard_distance_constraint_violation() {
+        // Master bedroom right next to living room
+        let placed_room = Room {
+            id: "master".to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 3.0,
+            height: 3.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "master".to_string(),
+            min_area: 9.0,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "living".to_string(),
+                min_distance: Some(6.0),
+                max_distance: None,
+                metric: DistanceMetric::CenterToCenter,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+
+        let already_placed = vec![Room {
+            id: "living".to_string(),
+            x: 3.0,
+            y: 0.0,
+            width: 3.0,
+            height: 3.0,
+        }];
+
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+
+        assert!(!is_valid);
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::DistanceOutOfRange { other_room_id, distance, .. } if other_room_id == "living" && *distance == 3.0
+        )));
+    }
+
+    // Test 23: test_soft_distance_constraint_adds_weight_when_met
+    #[test]
+    fn test_soft_distance_constraint_adds_weight_when_met() {
+        // Dining at (2.0, 2.0), kitchen 2 m away edge to edge
+        let placed_room = Room {
+            id: "dining".to_string(),
+            x: 2.0,
+            y: 2.0,
+            width: 2.0,
+            height: 2.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "dining".to_string(),
+            min_area: 4.0,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "kitchen".to_string(),
+                min_distance: None,
+                max_distance: Some(4.0),
+                metric: DistanceMetric::EdgeToEdge,
+                hard: false,
+                weight: 4.0,
+            }],
+            ..Default::default()
+        };
+
+        let already_placed = vec![Room {
+            id: "kitchen".to_string(),
+            x: 6.0,
+            y: 2.0,
+            width: 2.0,
+            height: 2.0,
+        }];
+
+        let score = calculate_soft_preference_score(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+        assert_eq!(score, 4.0);
+
+        // A soft constraint never makes the placement invalid
+        let (is_valid, _) = check_hard_constraints(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+        assert!(is_valid);
+    }
+
+    // Test 24: test_violation_when_not_on_required_side
+    #[test]
+    fn test_violation_when_not_on_required_side() {
+        // Garage touches the top wall but must be on the bottom (street) side
+        let placed_room = Room {
+            id: "garage".to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 3.0,
+            height: 6.0,
+        };
+
+        let room_req = RoomRequirement {
+            id: "garage".to_string(),
+            min_area: 18.0,
+            exterior_sides: vec![BoundarySide::Bottom],
+            ..Default::default()
+        };
+
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &[], 10.0, 10.0, &SolverConfig::default());
+        assert!(!is_valid);
+        assert_eq!(
+            violations,
+            vec![Violation::NotOnRequiredSide {
+                room_id: "garage".to_string(),
+                sides: vec![BoundarySide::Bottom],
+            }]
+        );
+
+        let on_street = Room { y: 4.0, ..placed_room };
+        let (is_valid, _) = check_hard_constraints(&on_street, &room_req, &[], 10.0, 10.0, &SolverConfig::default());
+        assert!(is_valid);
+    }
+
+    // Test 25: test_relative_position_hard_and_soft
+    #[test]
+    fn test_relative_position_hard_and_soft() {
+        // Bedroom directly above the living room
+        let placed_room = Room {
+            id: "bedroom".to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 3.0,
+            height: 3.0,
+        };
+
+        let already_placed = vec![
+            Room { id: "living".to_string(), x: 0.0, y: 3.0, width: 5.0, height: 4.0 },
+            Room { id: "study".to_string(), x: 5.0, y: 0.0, width: 3.0, height: 3.0 },
+        ];
+
+        let room_req = RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 9.0,
+            position_constraints: vec![
+                PositionConstraint {
+                    room_id: "living".to_string(),
+                    position: RelativePosition::Above,
+                    hard: true,
+                    weight: 0.0,
+                },
+                PositionConstraint {
+                    room_id: "study".to_string(),
+                    position: RelativePosition::LeftOf,
+                    hard: false,
+                    weight: 2.0,
+                },
+                PositionConstraint {
+                    room_id: "living".to_string(),
+                    position: RelativePosition::RightOf,
+                    hard: false,
+                    weight: 4.0,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (is_valid, _) = check_hard_constraints(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+        assert!(is_valid);
+
+        // 2.0 for left of study, nothing for right of living, 3.0 exterior wall bonus
+        let score = calculate_soft_preference_score(&placed_room, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+        assert_eq!(score, 5.0);
+
+        let beside_living = Room { x: 5.0, y: 3.0, ..placed_room };
+        let (is_valid, violations) = check_hard_constraints(&beside_living, &room_req, &already_placed, 10.0, 10.0, &SolverConfig::default());
+        assert!(!is_valid);
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::RelativePositionViolated { position: RelativePosition::Above, .. }
+        )));
+    }
+
+    // Test 26: test_facade_orientation_and_length_follow_north_angle
+    #[test]
+    fn test_facade_orientation_and_length_follow_north_angle() {
+        // Living room along the bottom wall: 4 m of facade on the bottom, 2.5 m on the left
+        let placed_room = Room {
+            id: "living".to_string(),
+            x: 0.0,
+            y: 7.5,
+            width: 4.0,
+            height: 2.5,
+        };
+
+        let room_req = RoomRequirement {
+            id: "living".to_string(),
+            min_area: 10.0,
+            facade_orientations: vec![Orientation::South, Orientation::West],
+            min_facade_length: Some(3.0),
+            ..Default::default()
+        };
+
+        // Top faces north: bottom is south, left is west
+        let (is_valid, _) = check_hard_constraints(&placed_room, &room_req, &[], 10.0, 10.0, &SolverConfig::default());
+        assert!(is_valid);
+
+        // Site rotated so the top faces west: bottom is east, left is south (only 2.5 m)
+        let rotated = SolverConfig { north_angle: 90.0, ..Default::default() };
+        let (is_valid, violations) = check_hard_constraints(&placed_room, &room_req, &[], 10.0, 10.0, &rotated);
+        assert!(!is_valid);
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            Violation::InsufficientFacade { facade_length, .. } if *facade_length == 2.5
+        )));
+    }
+
+    // Test 27: test_alignment_groups_hard_and_soft
+    #[test]
+    fn test_alignment_groups_hard_and_soft() {
+        let placed = vec![Room { id: "bedroom1".to_string(), x: 0.0, y: 0.0, width: 3.0, height: 4.0 }];
+        let room_req = RoomRequirement { id: "bedroom2".to_string(), min_area: 12.0, ..Default::default() };
+        let shallow = Room { id: "bedroom2".to_string(), x: 3.0, y: 0.0, width: 4.0, height: 3.0 };
+        let deep = Room { id: "bedroom2".to_string(), x: 3.0, y: 0.0, width: 3.0, height: 4.0 };
+        let group = |hard| AlignmentGroup {
+            room_ids: vec!["bedroom1".to_string(), "bedroom2".to_string()],
+            alignment: Alignment::SameDepth,
+            hard,
+            weight: 4.0,
+        };
+
+        let hard_config = SolverConfig::default().with_alignment_group(group(true));
+        let (is_valid, violations) = check_hard_constraints(&shallow, &room_req, &placed, 10.0, 10.0, &hard_config);
+        assert!(!is_valid);
+        assert_eq!(
+            violations,
+            vec![Violation::Misaligned {
+                room_id: "bedroom2".to_string(),
+                other_room_id: "bedroom1".to_string(),
+                alignment: Alignment::SameDepth,
+            }]
+        );
+        assert!(check_hard_constraints(&deep, &room_req, &placed, 10.0, 10.0, &hard_config).0);
+
+        // Soft groups add their weight only when aligned (both rooms touch the top wall either way)
+        let weights = ScoringWeights { soft_preference_cap: 100.0, ..Default::default() };
+        let soft_config = SolverConfig { weights, ..Default::default() }.with_alignment_group(group(false));
+        let aligned = calculate_soft_preference_score(&deep, &room_req, &placed, 10.0, 10.0, &soft_config);
+        let misaligned = calculate_soft_preference_score(&shallow, &room_req, &placed, 10.0, 10.0, &soft_config);
+        assert_eq!(aligned - misaligned, 4.0);
+    }
+
+    // Test 28: test_zone_gradient_from_entrance
+    #[test]
+    fn test_zone_gradient_from_entrance() {
+        let near = Room { id: "room".to_string(), x: 0.0, y: 0.0, width: 2.0, height: 2.0 };
+        let far = Room { id: "room".to_string(), x: 8.0, y: 8.0, width: 2.0, height: 2.0 };
+        let zoned = |zone| RoomRequirement { id: "room".to_string(), min_area: 4.0, zone: Some(zone), ..Default::default() };
+        let config = SolverConfig { entrances: vec![Entrance { x: 0.0, y: 0.0 }], ..Default::default() };
+
+        let score = |room: &Room, zone| zone_gradient_score(&Rectangle::from_room(room), &zoned(zone), 10.0, 10.0, &config);
+        assert!(score(&near, Zone::Public) > score(&far, Zone::Public));
+        assert!(score(&far, Zone::Private) > score(&near, Zone::Private));
+        assert_eq!(score(&near, Zone::Service), 0.0);
+
+        // No entrance, no gradient
+        let no_entrance = zone_gradient_score(&Rectangle::from_room(&near), &zoned(Zone::Public), 10.0, 10.0, &SolverConfig::default());
+        assert_eq!(no_entrance, 0.0);
+    }
+
+    // Test 29: test_incoming_distance_constraint_checked_on_target
+    #[test]
+    fn test_incoming_distance_constraint_checked_on_target() {
+        let master = Room { id: "master".to_string(), x: 0.0, y: 0.0, width: 3.0, height: 3.0 };
+        let master_req = RoomRequirement {
+            id: "master".to_string(),
+            min_area: 9.0,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "living".to_string(),
+                min_distance: Some(6.0),
+                max_distance: None,
+                metric: DistanceMetric::CenterToCenter,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+        let living_req = RoomRequirement { id: "living".to_string(), min_area: 9.0, ..Default::default() };
+        let requirements = vec![master_req, living_req];
+
+        // The owner was placed first, so the constraint is checked when living is placed
+        let near = Room { id: "living".to_string(), x: 3.0, y: 0.0, width: 3.0, height: 3.0 };
+        let far = Room { id: "living".to_string(), x: 6.0, y: 6.0, width: 3.0, height: 3.0 };
+        let placed = vec![master];
+        assert!(matches!(
+            check_incoming_constraints(&near, &placed, &requirements).as_slice(),
+            [Violation::DistanceOutOfRange { room_id, distance, .. }] if room_id == "master" && *distance == 3.0
+        ));
+        assert!(check_incoming_constraints(&far, &placed, &requirements).is_empty());
+    }
+
+    // Test 30: test_incoming_adjacency_relations_checked_on_target
+    #[test]
+    fn test_incoming_adjacency_relations_checked_on_target() {
+        let bath = Room { id: "bath".to_string(), x: 0.0, y: 0.0, width: 2.0, height: 2.0 };
+        let bath_req = RoomRequirement {
+            id: "bath".to_string(),
+            min_area: 4.0,
+            adjacent_to: vec!["bed".to_string()],
+            not_adjacent_to: vec!["kitchen".to_string()],
+            ..Default::default()
+        };
+        let requirements = vec![bath_req];
+        let placed = vec![bath];
+
+        let apart = |id: &str| Room { id: id.to_string(), x: 5.0, y: 0.0, width: 3.0, height: 3.0 };
+        let touching = |id: &str| Room { id: id.to_string(), x: 2.0, y: 0.0, width: 3.0, height: 3.0 };
+        assert_eq!(
+            check_incoming_constraints(&apart("bed"), &placed, &requirements),
+            vec![Violation::MissingAdjacency { room_id: "bath".to_string(), required_room_id: "bed".to_string() }]
+        );
+        assert!(check_incoming_constraints(&touching("bed"), &placed, &requirements).is_empty());
+        assert!(matches!(
+            check_incoming_constraints(&touching("kitchen"), &placed, &requirements).as_slice(),
+            [Violation::ForbiddenAdjacency { room_id, shared_edge_length, .. }]
+                if room_id == "bath" && *shared_edge_length == 2.0
+        ));
+        assert!(check_incoming_constraints(&apart("kitchen"), &placed, &requirements).is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::annealing::{anneal, annealing_cost, AnnealingSettings, Rng};
+use crate::candidate_generation::{round_up_to_grid, GRID_SIZE};
+use crate::scoring::LayoutScore;
+use crate::types::{Room, RoomRequirement};
+
+/// A packing of rooms encoded as two orderings plus a size per room.
+///
+/// Room `a` lies left of room `b` when it comes first in both sequences,
+/// and above `b` when it comes after `b` in `positive` but before it in
+/// `negative`. Every pair of rooms is related one way or the other, so a
+/// decoded layout never has overlapping rooms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequencePair {
+    pub positive: Vec<usize>,
+    pub negative: Vec<usize>,
+    /// Width and height of each room, indexed like the requirements.
+    pub sizes: Vec<(f64, f64)>,
+}
+
+impl SequencePair {
+    /// Rooms in a roughly square grid, each close to square at its `min_area`.
+    pub fn new(rooms: &[RoomRequirement]) -> Self {
+        let columns = (rooms.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let cell = |room: &usize| (room / columns, room % columns);
+
+        // Row by row for `negative`; column by column, bottom row first, for `positive`
+        let negative: Vec<usize> = (0..rooms.len()).collect();
+        let mut positive = negative.clone();
+        positive.sort_by_key(|room| {
+            let (row, column) = cell(room);
+            (column, std::cmp::Reverse(row))
+        });
+
+        let sizes = rooms.iter().map(|room_req| size_for_width(room_req, room_req.min_area.sqrt())).collect();
+        Self { positive, negative, sizes }
+    }
+
+    /// Place the rooms as far up and left as the sequences allow.
+    ///
+    /// Rooms are returned in requirement order, positioned by longest paths
+    /// through the left-of and above relations.
+    pub fn decode(&self, rooms: &[RoomRequirement]) -> Vec<Room> {
+        let mut positive_index = vec![0; self.positive.len()];
+        for (i, &room) in self.positive.iter().enumerate() {
+            positive_index[room] = i;
+        }
+
+        let mut positions = vec![(0.0, 0.0); self.sizes.len()];
+        for (i, &room) in self.negative.iter().enumerate() {
+            let (mut x, mut y) = (0.0f64, 0.0f64);
+            // Every room earlier in `negative` is either left of or above this one
+            for &other in self.negative[..i].iter() {
+                let (other_x, other_y) = positions[other];
+                let (other_width, other_height) = self.sizes[other];
+                if positive_index[other] < positive_index[room] {
+                    x = x.max(other_x + other_width);
+                } else {
+                    y = y.max(other_y + other_height);
+                }
+            }
+            positions[room] = (x, y);
+        }
+
+        rooms
+            .iter()
+            .zip(positions.iter().zip(self.sizes.iter()))
+            .map(|(room_req, (&(x, y), &(width, height)))| Room { id: room_req.id.clone(), x, y, width, height })
+            .collect()
+    }
+
+    /// A neighbouring encoding from one random move.
+    ///
+    /// The moves swap two rooms in either sequence or in both, rotate a
+    /// room, or make it one grid step wider or narrower at the same area.
+    /// Sizes always go through `size_for_width`, so rooms keep their
+    /// `min_area`, `min_width` and `min_depth`.
+    pub fn perturb(&self, rooms: &[RoomRequirement], rng: &mut Rng) -> Self {
+        let mut next = self.clone();
+        let room_count = self.sizes.len();
+        if room_count == 0 {
+            return next;
+        }
+
+        let move_count = if room_count < 2 { 2 } else { 5 };
+        match rng.below(move_count) {
+            0 => {
+                // Refitted like any other size, since a rotated room may fall short of its minimum width or depth
+                let room = rng.below(room_count);
+                next.sizes[room] = size_for_width(&rooms[room], next.sizes[room].1);
+            }
+            1 => {
+                let room = rng.below(room_count);
+                let width = next.sizes[room].0;
+                let width = if rng.below(2) == 0 { width - GRID_SIZE } else { width + GRID_SIZE };
+                next.sizes[room] = size_for_width(&rooms[room], width);
+            }
+            swap => {
+                let (a, b) = (rng.below(room_count), rng.below(room_count - 1));
+                let b = if b >= a { b + 1 } else { b };
+                // 2 swaps in `positive` only, 3 in `negative` only, 4 in both
+                if swap != 3 {
+                    swap_rooms(&mut next.positive, a, b);
+                }
+                if swap != 2 {
+                    swap_rooms(&mut next.negative, a, b);
+                }
+            }
+        }
+        next
+    }
+}
+
+/// Swap the places of two rooms in a sequence.
+fn swap_rooms(sequence: &mut [usize], a: usize, b: usize) {
+    let position = |room: usize| sequence.iter().position(|&r| r == room).expect("every room is in both sequences");
+    let (i, j) = (position(a), position(b));
+    sequence.swap(i, j);
+}
+
+/// A grid-aligned size with about the given width that covers `min_area`
+/// and the room's minimum width and depth.
+fn size_for_width(room_req: &RoomRequirement, width: f64) -> (f64, f64) {
+    let width = round_up_to_grid(width.max(room_req.min_width.unwrap_or(0.0))).max(GRID_SIZE);
+    let height = round_up_to_grid((room_req.min_area / width).max(room_req.min_depth.unwrap_or(0.0))).max(GRID_SIZE);
+    (width, height)
+}
+
+/// Pack rooms into the boundary by simulated annealing over sequence pairs.
+///
+/// The search starts from `SequencePair::new` and moves with
+/// `SequencePair::perturb`, so no grid positions are enumerated; rooms that
+/// end up outside the boundary count as violations in `evaluate`. Returns
+/// the best layout found if it is valid.
+pub fn solve_sequence_pair(
+    rooms: &[RoomRequirement],
+    settings: &AnnealingSettings,
+    evaluate: &dyn Fn(&[Room]) -> LayoutScore,
+) -> Option<Vec<Room>> {
+    let (best, _) = anneal(
+        SequencePair::new(rooms),
+        settings,
+        |pair, rng| pair.perturb(rooms, rng),
+        |pair| annealing_cost(&evaluate(&pair.decode(rooms))),
+    );
+
+    let layout = best.decode(rooms);
+    evaluate(&layout).is_valid().then_some(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annealing::rooms_with_areas;
+    use crate::geometry::Rectangle;
+
+    // Test 1: test_decoder_never_overlaps
+    #[test]
+    fn test_decoder_never_overlaps() {
+        let mut room_reqs = rooms_with_areas(&[12.0, 9.0, 7.0, 6.0, 4.0, 3.0, 2.5]);
+        room_reqs[2].min_width = Some(3.0);
+        room_reqs[4].min_depth = Some(2.5);
+        let mut rng = Rng::new(11);
+        let mut pair = SequencePair::new(&room_reqs);
+
+        for _ in 0..1000 {
+            pair = pair.perturb(&room_reqs, &mut rng);
+            let layout = pair.decode(&room_reqs);
+            for (i, room) in layout.iter().enumerate() {
+                let rect = Rectangle::from_room(room);
+                assert!(room.x >= 0.0 && room.y >= 0.0);
+                assert!(room.width * room.height >= room_reqs[i].min_area);
+                assert!(room_reqs[i].min_width.is_none_or(|min_width| room.width >= min_width));
+                assert!(room_reqs[i].min_depth.is_none_or(|min_depth| room.height >= min_depth));
+                assert!(layout[i + 1..].iter().all(|other| !rect.overlaps_with(&Rectangle::from_room(other))));
+            }
+        }
+    }
+
+    // Test 2: test_decode_follows_sequence_relations
+    #[test]
+    fn test_decode_follows_sequence_relations() {
+        // 1 is above 0, and 2 is right of both
+        let pair = SequencePair {
+            positive: vec![0, 1, 2],
+            negative: vec![1, 0, 2],
+            sizes: vec![(4.0, 3.0), (4.0, 2.0), (2.0, 5.0)],
+        };
+
+        let layout = pair.decode(&rooms_with_areas(&[12.0, 8.0, 10.0]));
+
+        let positions: Vec<(f64, f64)> = layout.iter().map(|r| (r.x, r.y)).collect();
+        assert_eq!(positions, vec![(0.0, 2.0), (0.0, 0.0), (4.0, 0.0)]);
+
+        let squares = rooms_with_areas(&[4.0; 4]);
+        let grid: Vec<(f64, f64)> = SequencePair::new(&squares).decode(&squares).iter().map(|r| (r.x, r.y)).collect();
+        assert_eq!(grid, vec![(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (2.0, 2.0)]);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::annealing::{anneal, annealing_cost, AnnealingSettings, Rng};
+use crate::candidate_generation::GRID_SIZE;
+use crate::geometry::Rectangle;
+use crate::scoring::LayoutScore;
+use crate::types::{Room, RoomRequirement};
+
+/// Attempts at a move that keeps the expression normalised before giving up.
+const PERTURB_ATTEMPTS: usize = 20;
+
+/// Direction of the cut line that divides a slicing tree node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cut {
+    /// A horizontal line: the first part lies above the second.
+    Horizontal,
+    /// A vertical line: the first part lies left of the second.
+    Vertical,
+}
+
+impl Cut {
+    fn flipped(self) -> Cut {
+        match self {
+            Cut::Horizontal => Cut::Vertical,
+            Cut::Vertical => Cut::Horizontal,
+        }
+    }
+}
+
+/// A guillotine floorplan: the boundary cut recursively into rooms.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlicingTree {
+    /// A room, by its index in the requirements.
+    Room(usize),
+    Cut { cut: Cut, first: Box<SlicingTree>, second: Box<SlicingTree> },
+}
+
+impl SlicingTree {
+    /// Indices of the rooms in this subtree.
+    pub fn rooms(&self) -> Vec<usize> {
+        match self {
+            SlicingTree::Room(room) => vec![*room],
+            SlicingTree::Cut { first, second, .. } => {
+                let mut rooms = first.rooms();
+                rooms.extend(second.rooms());
+                rooms
+            }
+        }
+    }
+
+    /// Place the rooms inside `rect`, which they fill exactly.
+    ///
+    /// Each cut divides its rectangle in proportion to the `min_area` of the
+    /// rooms on either side, with the cut line on the grid and at least one
+    /// grid step from either edge when the rectangle is wide enough. A
+    /// narrower rectangle goes entirely to one side, leaving the other rooms
+    /// empty for `evaluate` to reject.
+    pub fn layout(&self, rooms: &[RoomRequirement], rect: Rectangle) -> Vec<Room> {
+        let mut placed = Vec::new();
+        self.layout_into(rooms, rect, &mut placed);
+        placed
+    }
+
+    fn layout_into(&self, rooms: &[RoomRequirement], rect: Rectangle, placed: &mut Vec<Room>) {
+        let (cut, first, second) = match self {
+            SlicingTree::Room(room) => {
+                placed.push(Room {
+                    id: rooms[*room].id.clone(),
+                    x: rect.x,
+                    y: rect.y,
+                    width: rect.width,
+                    height: rect.height,
+                });
+                return;
+            }
+            SlicingTree::Cut { cut, first, second } => (cut, first, second),
+        };
+
+        let demand = |tree: &SlicingTree| tree.rooms().iter().map(|&room| rooms[room].min_area).sum::<f64>();
+        let share = demand(first) / (demand(first) + demand(second));
+        let split = |start: f64, length: f64| {
+            let line = ((start + length * share) / GRID_SIZE).round() * GRID_SIZE;
+            let clamp_to_grid = |from: f64, to: f64| {
+                let lowest = (from / GRID_SIZE - 1e-9).ceil() * GRID_SIZE;
+                let highest = (to / GRID_SIZE + 1e-9).floor() * GRID_SIZE;
+                (lowest <= highest).then(|| line.clamp(lowest, highest))
+            };
+            // Without a grid line a step in from both edges, one part is left empty
+            clamp_to_grid(start + GRID_SIZE, start + length - GRID_SIZE)
+                .or_else(|| clamp_to_grid(start, start + length))
+                .unwrap_or(start)
+        };
+
+        let (first_rect, second_rect) = match cut {
+            Cut::Vertical => {
+                let line = split(rect.x, rect.width);
+                (
+                    Rectangle { width: line - rect.x, ..rect },
+                    Rectangle { x: line, width: rect.x + rect.width - line, ..rect },
+                )
+            }
+            Cut::Horizontal => {
+                let line = split(rect.y, rect.height);
+                (
+                    Rectangle { height: line - rect.y, ..rect },
+                    Rectangle { y: line, height: rect.y + rect.height - line, ..rect },
+                )
+            }
+        };
+        first.layout_into(rooms, first_rect, placed);
+        second.layout_into(rooms, second_rect, placed);
+    }
+}
+
+/// One token of a Polish expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolishToken {
+    Room(usize),
+    /// Joins the two subtrees before it.
+    Cut(Cut),
+}
+
+/// A slicing tree in postfix (Polish) notation.
+///
+/// Normalised expressions never repeat a cut directly, so each slicing
+/// floorplan has exactly one; they are what the annealing moves explore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolishExpression {
+    pub tokens: Vec<PolishToken>,
+}
+
+impl PolishExpression {
+    /// Rooms `0..room_count` joined one at a time by alternating cuts.
+    pub fn new(room_count: usize) -> Self {
+        let mut tokens = Vec::new();
+        for room in 0..room_count {
+            tokens.push(PolishToken::Room(room));
+            if room > 0 {
+                let cut = if room % 2 == 1 { Cut::Vertical } else { Cut::Horizontal };
+                tokens.push(PolishToken::Cut(cut));
+            }
+        }
+        Self { tokens }
+    }
+
+    /// Whether the tokens form a normalised expression.
+    ///
+    /// Every prefix must hold more rooms than cuts, the whole expression
+    /// exactly one more, and no cut may directly follow an equal one.
+    pub fn is_normalized(&self) -> bool {
+        let mut depth = 0usize;
+        let mut previous_cut = None;
+        for token in self.tokens.iter() {
+            match token {
+                PolishToken::Room(_) => {
+                    depth += 1;
+                    previous_cut = None;
+                }
+                PolishToken::Cut(cut) => {
+                    if depth < 2 || previous_cut == Some(*cut) {
+                        return false;
+                    }
+                    depth -= 1;
+                    previous_cut = Some(*cut);
+                }
+            }
+        }
+        depth == 1
+    }
+
+    /// The slicing tree the expression describes, or `None` when it is malformed.
+    pub fn to_tree(&self) -> Option<SlicingTree> {
+        let mut stack: Vec<SlicingTree> = Vec::new();
+        for token in self.tokens.iter() {
+            match token {
+                PolishToken::Room(room) => stack.push(SlicingTree::Room(*room)),
+                PolishToken::Cut(cut) => {
+                    let second = stack.pop()?;
+                    let first = stack.pop()?;
+                    stack.push(SlicingTree::Cut { cut: *cut, first: Box::new(first), second: Box::new(second) });
+                }
+            }
+        }
+        if stack.len() == 1 { stack.pop() } else { None }
+    }
+
+    /// A neighbouring normalised expression, from one of Wong and Liu's moves.
+    ///
+    /// The moves swap two rooms next to each other in the expression, flip
+    /// every cut in a chain of cuts, or swap a room with an adjacent cut.
+    /// Returns the expression unchanged if no move applies.
+    pub fn perturb(&self, rng: &mut Rng) -> Self {
+        let is_room = |token: &PolishToken| matches!(token, PolishToken::Room(_));
+        for _ in 0..PERTURB_ATTEMPTS {
+            let mut next = self.clone();
+            let tokens = &mut next.tokens;
+            match rng.below(3) {
+                0 => {
+                    let rooms: Vec<usize> = (0..tokens.len()).filter(|&i| is_room(&tokens[i])).collect();
+                    if rooms.len() < 2 {
+                        continue;
+                    }
+                    let i = rng.below(rooms.len() - 1);
+                    tokens.swap(rooms[i], rooms[i + 1]);
+                }
+                1 => {
+                    let cuts: Vec<usize> = (0..tokens.len()).filter(|&i| !is_room(&tokens[i])).collect();
+                    if cuts.is_empty() {
+                        continue;
+                    }
+                    let mut start = cuts[rng.below(cuts.len())];
+                    while start > 0 && !is_room(&tokens[start - 1]) {
+                        start -= 1;
+                    }
+                    for token in tokens[start..].iter_mut() {
+                        let PolishToken::Cut(cut) = token else { break };
+                        *cut = cut.flipped();
+                    }
+                }
+                _ => {
+                    if tokens.len() < 2 {
+                        continue;
+                    }
+                    let i = rng.below(tokens.len() - 1);
+                    if is_room(&tokens[i]) == is_room(&tokens[i + 1]) {
+                        continue;
+                    }
+                    tokens.swap(i, i + 1);
+                }
+            }
+
+            if next.is_normalized() && next != *self {
+                return next;
+            }
+        }
+        self.clone()
+    }
+}
+
+/// Lay out rooms as a slicing floorplan found by simulated annealing.
+///
+/// The search starts from `PolishExpression::new` and moves through
+/// normalised Polish expressions with `PolishExpression::perturb`. Every
+/// expression lays out to rooms that exactly fill the boundary, so the
+/// search only trades off the scores `evaluate` gives. Returns the best
+/// layout found if it is valid.
+pub fn solve_slicing_tree(
+    rooms: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    settings: &AnnealingSettings,
+    evaluate: &dyn Fn(&[Room]) -> LayoutScore,
+) -> Option<Vec<Room>> {
+    if rooms.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let boundary = Rectangle { x: 0.0, y: 0.0, width: boundary_width, height: boundary_height };
+    let layout = |expression: &PolishExpression| {
+        expression.to_tree().map(|tree| tree.layout(rooms, boundary)).unwrap_or_default()
+    };
+    let (best, _) = anneal(
+        PolishExpression::new(rooms.len()),
+        settings,
+        |expression, rng| expression.perturb(rng),
+        |expression| annealing_cost(&evaluate(&layout(expression))),
+    );
+
+    let layout = layout(&best);
+    evaluate(&layout).is_valid().then_some(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annealing::rooms_with_areas;
+
+    // Test 1: test_expressions_stay_normalised_and_gap_free
+    #[test]
+    fn test_expressions_stay_normalised_and_gap_free() {
+        let room_reqs = rooms_with_areas(&[12.0, 9.0, 6.0, 6.0, 4.0, 3.0]);
+        let boundary = Rectangle { x: 0.0, y: 0.0, width: 10.0, height: 8.0 };
+        let mut rng = Rng::new(7);
+        let mut expression = PolishExpression::new(room_reqs.len());
+
+        for _ in 0..500 {
+            expression = expression.perturb(&mut rng);
+            assert!(expression.is_normalized());
+
+            let layout = expression.to_tree().unwrap().layout(&room_reqs, boundary);
+            assert_eq!(layout.len(), room_reqs.len());
+            let total: f64 = layout.iter().map(|r| r.width * r.height).sum();
+            assert!((total - 80.0).abs() < 1e-9);
+            for (i, room) in layout.iter().enumerate() {
+                let rect = Rectangle::from_room(room);
+                assert!(rect.width > 0.0 && rect.height > 0.0 && rect.is_within_boundary(10.0, 8.0));
+                assert!(layout[i + 1..].iter().all(|other| !rect.overlaps_with(&Rectangle::from_room(other))));
+            }
+        }
+    }
+
+    // Test 2: test_tree_cuts_split_by_area
+    #[test]
+    fn test_tree_cuts_split_by_area() {
+        use PolishToken::{Cut as C, Room as R};
+
+        // (0 | 1) over 2
+        let expression = PolishExpression { tokens: vec![R(0), R(1), C(Cut::Vertical), R(2), C(Cut::Horizontal)] };
+        assert!(expression.is_normalized());
+        assert!(!PolishExpression { tokens: vec![R(0), R(1), R(2), C(Cut::Vertical), C(Cut::Vertical)] }.is_normalized());
+        assert!(!PolishExpression { tokens: vec![R(0), C(Cut::Vertical), R(1)] }.is_normalized());
+
+        let boundary = Rectangle { x: 0.0, y: 0.0, width: 10.0, height: 8.0 };
+        let layout = expression.to_tree().unwrap().layout(&rooms_with_areas(&[30.0, 10.0, 40.0]), boundary);
+        let sizes: Vec<(f64, f64, f64, f64)> = layout.iter().map(|r| (r.x, r.y, r.width, r.height)).collect();
+        assert_eq!(sizes, vec![(0.0, 0.0, 7.5, 4.0), (7.5, 0.0, 2.5, 4.0), (0.0, 4.0, 10.0, 4.0)]);
+    }
+
+    // Test 3: test_narrow_cuts_stay_on_grid
+    #[test]
+    fn test_narrow_cuts_stay_on_grid() {
+        let tree = SlicingTree::Cut {
+            cut: Cut::Vertical,
+            first: Box::new(SlicingTree::Room(0)),
+            second: Box::new(SlicingTree::Room(1)),
+        };
+        let room_reqs = rooms_with_areas(&[4.0, 4.0]);
+        let lines = |x: f64, width: f64| -> Vec<(f64, f64)> {
+            let layout = tree.layout(&room_reqs, Rectangle { x, y: 0.0, width, height: 4.0 });
+            layout.iter().map(|r| (r.x, r.width)).collect()
+        };
+
+        assert_eq!(lines(2.0, 0.5), vec![(2.0, 0.5), (2.5, 0.0)]);
+        // The far edge is off the grid, so the inner lines stop at the last grid line before it
+        assert_eq!(lines(0.0, 1.3), vec![(0.0, 0.5), (0.5, 0.8)]);
+        assert_eq!(lines(0.0, 0.3), vec![(0.0, 0.0), (0.0, 0.3)]);
+    }
+}
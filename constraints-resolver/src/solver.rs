@@ -1,19 +1,90 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
 use crate::{
-    candidate_generation::generate_candidate_positions,
+    candidate_generation::{
+        generate_aligned_candidate_positions, generate_candidate_positions, generate_walled_candidate_positions,
+    },
+    circulation::{generate_corridors, stranded_rooms, unreachable_rooms},
+    doors::{place_doors, Door},
+    config::{SolverConfig, SolverStrategy},
+    feasibility::{check_feasibility, Infeasibility},
+    fill::{fill_layout, net_to_gross},
+    free_space::{analyze_free_space, FreeSpace},
+    instances::expand_instances,
+    geometry::Rectangle,
+    rectangular_dual::{solve_rectangular_dual, RectangularDualError},
     room_ordering::order_rooms_by_constraints,
-    scoring::score_position,
-    types::{Room, RoomRequirement},
+    scoring::{check_distance_constraints, check_incoming_constraints, score_position, LayoutScore},
+    sequence_pair::solve_sequence_pair,
+    slicing_tree::solve_slicing_tree,
+    types::{Room, RoomRequirement, Zone},
+    validation::{validate_config, validate_entrances, validate_requirements, ValidationError},
+    windows::{place_windows, Window},
+    zoning::{check_zone_contiguity, is_zone_contiguous, room_zones},
 };
 
 pub struct LayoutSolution {
     pub rooms: Vec<Room>,
     pub total_score: f64,
     pub is_valid: bool,
+    /// Rooms derived by mirroring another room, from the config's mirror groups.
+    pub mirrored_rooms: Vec<MirroredRoom>,
+    /// Optional room instances that could not be placed.
+    pub dropped_rooms: Vec<String>,
+    /// Corridors (when `SolverConfig::corridor_width` is set) followed by space
+    /// not assigned to any room (when `SolverConfig::fill` is set).
+    pub circulation: Vec<Room>,
+    /// Rooms inside their walls, in the same order as `rooms`; equal to `rooms`
+    /// when `SolverConfig::walls` is not set.
+    pub net_rooms: Vec<Room>,
+    /// Net room area over boundary area.
+    pub net_to_gross: f64,
+    /// Unused parts of the boundary (circulation counts as unused).
+    pub free_space: FreeSpace,
+    /// Rooms with no route from an entrance through rooms and circulation
+    /// (empty when there are no entrances).
+    pub unreachable_rooms: Vec<String>,
+    /// One door per required connection, on the wall the two rooms share.
+    pub doors: Vec<Door>,
+    /// Required connections whose shared wall is too short for a door.
+    pub blocked_doors: Vec<(String, String)>,
+    /// Windows on the exterior walls of rooms with a glazing ratio.
+    pub windows: Vec<Window>,
+    /// Rooms whose exterior walls cannot take the glazing they need.
+    pub underglazed_rooms: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+/// A room placed as the mirror image of another room.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirroredRoom {
+    pub room_id: String,
+    pub source_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "details", rename_all = "snake_case")]
 pub enum SolverError {
     NoSolutionFound(String),
+    InvalidRequirements(Vec<ValidationError>),
+    Infeasible(Infeasibility),
+    NoRectangularDual(RectangularDualError),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::NoSolutionFound(message) => write!(f, "{}", message),
+            SolverError::InvalidRequirements(errors) => {
+                let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                write!(f, "Invalid requirements: {}", messages.join("; "))
+            }
+            SolverError::Infeasible(infeasibility) => write!(f, "{}", infeasibility),
+            SolverError::NoRectangularDual(error) => write!(f, "{}", error),
+        }
+    }
 }
 
 pub fn solve_layout(
@@ -21,30 +92,130 @@ pub fn solve_layout(
     boundary_width: f64,
     boundary_height: f64,
 ) -> Result<LayoutSolution, SolverError> {
-    let ordered_rooms = order_rooms_by_constraints(room_requirements);
+    solve_layout_with_config(room_requirements, boundary_width, boundary_height, &SolverConfig::default())
+}
+
+/// Solve a layout with custom scoring weights and objectives.
+///
+/// Requirements are expanded into room instances first. Required instances
+/// are placed by `config.strategy`, backtracking search by default; optional
+/// ones are then added one at a time by backtracking, highest priority first,
+/// and dropped when they do not fit. With
+/// `config.fill` set, rooms then grow to cover the boundary. Doors and windows
+/// are placed last, on the final room outlines.
+pub fn solve_layout_with_config(
+    room_requirements: Vec<RoomRequirement>,
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> Result<LayoutSolution, SolverError> {
+    validate_requirements(&room_requirements).map_err(SolverError::InvalidRequirements)?;
+    validate_entrances(&config.entrances, boundary_width, boundary_height).map_err(SolverError::InvalidRequirements)?;
+    // Generated instance ids may collide with ids the caller chose
+    let room_requirements = expand_instances(&room_requirements);
+    validate_requirements(&room_requirements).map_err(SolverError::InvalidRequirements)?;
+    validate_config(&room_requirements, config, boundary_width, boundary_height)
+        .map_err(SolverError::InvalidRequirements)?;
+
+    let (optional_rooms, required_rooms): (Vec<RoomRequirement>, Vec<RoomRequirement>) =
+        room_requirements.iter().cloned().partition(|room_req| room_req.optional);
+    check_feasibility(&required_rooms, boundary_width, boundary_height).map_err(SolverError::Infeasible)?;
+
+    let room_zones = room_zones(&room_requirements);
+    let ordered_rooms = order_rooms_by_constraints(required_rooms);
+
+    // Mirrored counterparts are derived from their sources rather than searched
+    let (derived_rooms, searched_rooms): (Vec<RoomRequirement>, Vec<RoomRequirement>) = room_requirements
+        .iter()
+        .cloned()
+        .partition(|room_req| config.mirror_source(&room_req.id).is_some());
+    let is_searched = |room_req: &RoomRequirement| searched_rooms.iter().any(|r| r.id == room_req.id);
+    let ordered_rooms: Vec<RoomRequirement> = ordered_rooms.into_iter().filter(is_searched).collect();
 
     let already_placed: Vec<Room> = Vec::new();
 
-    let solution = solve_recursive(
-        &ordered_rooms,
-        already_placed,
-        boundary_width,
-        boundary_height,
-    );
+    let evaluate = |rooms: &[Room]| {
+        evaluate_layout(rooms, &room_requirements, boundary_width, boundary_height, config, &room_zones)
+    };
+    let solution = match config.strategy {
+        SolverStrategy::Backtracking => solve_recursive(
+            &ordered_rooms,
+            &room_requirements,
+            already_placed,
+            boundary_width,
+            boundary_height,
+            config,
+            &room_zones,
+        ),
+        SolverStrategy::RectangularDual => {
+            let rooms = solve_rectangular_dual(&ordered_rooms, boundary_width, boundary_height, config, &evaluate)
+                .map_err(SolverError::NoRectangularDual)?;
+            Some(rooms)
+        }
+        SolverStrategy::SlicingTree => {
+            solve_slicing_tree(&ordered_rooms, boundary_width, boundary_height, &config.annealing, &evaluate)
+        }
+        SolverStrategy::SequencePair => solve_sequence_pair(&ordered_rooms, &config.annealing, &evaluate),
+    };
+
+    // Add optional rooms on top of the required layout, keeping each one that fits
+    let mut optional_rooms: Vec<RoomRequirement> = optional_rooms.into_iter().filter(is_searched).collect();
+    optional_rooms.sort_by_key(|room_req| std::cmp::Reverse(room_req.priority));
+    let solution = solution.map(|mut placed_rooms| {
+        for optional_room in optional_rooms.iter() {
+            if let Some(with_optional) = solve_recursive(
+                std::slice::from_ref(optional_room),
+                &room_requirements,
+                placed_rooms.clone(),
+                boundary_width,
+                boundary_height,
+                config,
+                &room_zones,
+            ) {
+                placed_rooms = with_optional;
+            }
+        }
+        placed_rooms
+    });
 
     match solution {
-        Some(placed_rooms) => {
+        Some(mut placed_rooms) => {
+            let mut circulation = match config.corridor_width {
+                Some(corridor_width) => {
+                    let plan = generate_corridors(
+                        &placed_rooms,
+                        &room_requirements,
+                        &config.entrances,
+                        corridor_width,
+                        boundary_width,
+                        boundary_height,
+                    );
+                    plan.corridors
+                }
+                None => Vec::new(),
+            };
+            if config.fill {
+                let leftover = fill_layout(
+                    &mut placed_rooms,
+                    &room_requirements,
+                    &circulation,
+                    boundary_width,
+                    boundary_height,
+                    config,
+                );
+                circulation.extend(leftover);
+            }
+
             // Calculate total score by scoring each placed room
             let mut total_score = 0.0;
 
             for (i, room) in placed_rooms.iter().enumerate() {
-                let room_req = ordered_rooms
+                let room_req = room_requirements
                     .iter()
                     .find(|r| r.id == room.id)
-                    .expect(&format!(
-                        "Room requirement not found for placed room: {}",
-                        room.id
-                    ));
+                    .unwrap_or_else(|| {
+                        panic!("Room requirement not found for placed room: {}", room.id)
+                    });
 
                 // Get all rooms placed before this one
                 let already_placed_before = placed_rooms[..i].to_vec();
@@ -55,15 +226,58 @@ pub fn solve_layout(
                     &already_placed_before,
                     boundary_width,
                     boundary_height,
+                    config,
                 );
 
                 total_score += score.total_score;
+                total_score += check_zone_contiguity(room, &already_placed_before, &room_zones, config).unwrap_or(0.0);
             }
 
+            let is_placed = |room_id: &str| placed_rooms.iter().any(|r| r.id == room_id);
+            let mirrored_rooms = derived_rooms
+                .iter()
+                .filter(|room_req| is_placed(&room_req.id))
+                .filter_map(|room_req| {
+                    config.mirror_source(&room_req.id).map(|source_id| MirroredRoom {
+                        room_id: room_req.id.clone(),
+                        source_id: source_id.to_string(),
+                    })
+                })
+                .collect();
+            let dropped_rooms = room_requirements
+                .iter()
+                .filter(|room_req| !is_placed(&room_req.id))
+                .map(|room_req| room_req.id.clone())
+                .collect();
+            let unreachable = if config.entrances.is_empty() {
+                Vec::new()
+            } else {
+                let spaces: Vec<Room> = placed_rooms.iter().chain(circulation.iter()).cloned().collect();
+                unreachable_rooms(&spaces, &room_requirements, &config.entrances)
+            };
+
+            let net_rooms: Vec<Room> = placed_rooms
+                .iter()
+                .map(|room| config.net_room(room, boundary_width, boundary_height))
+                .collect();
+            let door_plan = place_doors(&placed_rooms, &room_requirements, &config.doors);
+            let window_plan = place_windows(&placed_rooms, &room_requirements, boundary_width, boundary_height, config);
+
             Ok(LayoutSolution {
+                net_to_gross: net_to_gross(&net_rooms, boundary_width, boundary_height),
+                net_rooms,
+                free_space: analyze_free_space(&placed_rooms, boundary_width, boundary_height),
                 rooms: placed_rooms,
-                total_score: total_score,
+                total_score,
                 is_valid: true,
+                mirrored_rooms,
+                dropped_rooms,
+                circulation,
+                unreachable_rooms: unreachable,
+                doors: door_plan.doors,
+                blocked_doors: door_plan.blocked_connections,
+                windows: window_plan.windows,
+                underglazed_rooms: window_plan.underglazed_room_ids,
             })
         }
         None => Err(SolverError::NoSolutionFound(
@@ -72,23 +286,133 @@ pub fn solve_layout(
     }
 }
 
+/// Whether every room can be reached as the config requires: through
+/// corridors when `corridor_width` is set, otherwise through shared walls
+/// when `require_reachability` is set.
+fn is_reachable(
+    rooms: &[Room],
+    room_requirements: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> bool {
+    if let Some(corridor_width) = config.corridor_width {
+        let plan = generate_corridors(
+            rooms,
+            room_requirements,
+            &config.entrances,
+            corridor_width,
+            boundary_width,
+            boundary_height,
+        );
+        plan.unreachable_room_ids.is_empty()
+    } else {
+        !config.require_reachability || unreachable_rooms(rooms, room_requirements, &config.entrances).is_empty()
+    }
+}
+
+/// Score a complete layout and count the hard constraints it breaks.
+///
+/// Strategies that lay out every room at once use this in place of the
+/// per-room checks of the backtracking search: each room is checked against
+/// all the others, hard zone contiguity and distances over the whole
+/// layout, and reachability as in `is_reachable`. The score adds up `score_position` for
+/// each room against the rooms before it, as for a finished solution.
+fn evaluate_layout(
+    rooms: &[Room],
+    room_requirements: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+    room_zones: &HashMap<String, Zone>,
+) -> LayoutScore {
+    let mut layout_score = LayoutScore { total_score: 0.0, violations: 0 };
+    for (i, room) in rooms.iter().enumerate() {
+        let Some(room_req) = room_requirements.iter().find(|r| r.id == room.id) else { continue };
+        let others: Vec<Room> = rooms.iter().filter(|other| other.id != room.id).cloned().collect();
+        if score_position(room, room_req, &others, boundary_width, boundary_height, config).has_violations {
+            layout_score.violations += 1;
+        }
+
+        let score = score_position(room, room_req, &rooms[..i], boundary_width, boundary_height, config);
+        layout_score.total_score += score.total_score;
+        layout_score.total_score += check_zone_contiguity(room, &rooms[..i], room_zones, config).unwrap_or(0.0);
+    }
+
+    layout_score.violations += config
+        .zone_contiguity
+        .iter()
+        .filter(|constraint| constraint.hard && !is_zone_contiguous(constraint.zone, rooms, room_zones))
+        .count();
+    if !is_reachable(rooms, room_requirements, boundary_width, boundary_height, config) {
+        layout_score.violations += 1;
+    }
+    layout_score.violations += check_distance_constraints(rooms, room_requirements).len();
+    layout_score
+}
+
 fn solve_recursive(
     remaining_rooms: &[RoomRequirement],
+    room_requirements: &[RoomRequirement],
     already_placed: Vec<Room>,
     boundary_width: f64,
     boundary_height: f64,
+    config: &SolverConfig,
+    room_zones: &HashMap<String, Zone>,
 ) -> Option<Vec<Room>> {
-    // BASE CASE: No more rooms to place
+    // BASE CASE: No more rooms to place; every room must be reachable, through corridors when requested,
+    // zones left incomplete by optional rooms must still be contiguous, and walking distances are final
     if remaining_rooms.is_empty() {
+        let zones_contiguous = config
+            .zone_contiguity
+            .iter()
+            .all(|constraint| !constraint.hard || is_zone_contiguous(constraint.zone, &already_placed, room_zones));
+        if !zones_contiguous
+            || !is_reachable(&already_placed, room_requirements, boundary_width, boundary_height, config)
+            || !check_distance_constraints(&already_placed, room_requirements).is_empty()
+        {
+            return None;
+        }
         return Some(already_placed);
     }
 
+    // A room already cut off from every entrance stays cut off as more rooms are placed
+    if config.corridor_width.is_some() || config.require_reachability {
+        let unplaced: Vec<RoomRequirement> = room_requirements
+            .iter()
+            .filter(|room_req| already_placed.iter().all(|room| room.id != room_req.id))
+            .cloned()
+            .collect();
+        let stranded = stranded_rooms(
+            &already_placed,
+            &unplaced,
+            room_requirements,
+            &config.entrances,
+            config.corridor_width,
+            boundary_width,
+            boundary_height,
+        );
+        if !stranded.is_empty() {
+            return None;
+        }
+    }
+
     // RECURSIVE CASE: Place the next room
     let current_room = &remaining_rooms[0];
     let remaining_rooms = &remaining_rooms[1..];
 
     // Generate all candidate positions for current room
-    let candidates = generate_candidate_positions(current_room, boundary_width, boundary_height);
+    let mut candidates = match &config.walls {
+        Some(walls) => generate_walled_candidate_positions(current_room, walls, boundary_width, boundary_height),
+        None => generate_candidate_positions(current_room, boundary_width, boundary_height),
+    };
+    candidates.extend(generate_aligned_candidate_positions(
+        current_room,
+        &already_placed,
+        config,
+        boundary_width,
+        boundary_height,
+    ));
 
     // Score and sort candidates (best first)
     let mut scored_candidates = Vec::new();
@@ -99,32 +423,82 @@ fn solve_recursive(
             &already_placed,
             boundary_width,
             boundary_height,
+            config,
         );
 
-        if !score.has_violations {
-            scored_candidates.push((score, candidate));
+        if score.has_violations || !check_incoming_constraints(&candidate, &already_placed, room_requirements).is_empty()
+        {
+            continue;
         }
+        let Ok(zone_score) = check_zone_contiguity(&candidate, &already_placed, room_zones, config) else {
+            continue;
+        };
+
+        // A mirror source is only valid if its derived counterpart is valid too
+        let mut total_score = score.total_score + zone_score;
+        let mut mirrored = None;
+        if let Some((counterpart_id, axis)) = config.mirror_counterpart(&current_room.id)
+            && let Some(counterpart_req) = room_requirements.iter().find(|r| r.id == counterpart_id)
+        {
+            let rect = Rectangle::from_room(&candidate).mirrored(axis, boundary_width, boundary_height);
+            let counterpart = Room {
+                id: counterpart_req.id.clone(),
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+            };
+
+            let mut placed_with_source = already_placed.clone();
+            placed_with_source.push(candidate.clone());
+            let counterpart_score = score_position(
+                &counterpart,
+                counterpart_req,
+                &placed_with_source,
+                boundary_width,
+                boundary_height,
+                config,
+            );
+            if counterpart_score.has_violations
+                || !check_incoming_constraints(&counterpart, &placed_with_source, room_requirements).is_empty()
+            {
+                continue;
+            }
+            let Ok(counterpart_zone_score) =
+                check_zone_contiguity(&counterpart, &placed_with_source, room_zones, config)
+            else {
+                continue;
+            };
+
+            total_score += counterpart_score.total_score + counterpart_zone_score;
+            mirrored = Some(counterpart);
+        }
+
+        scored_candidates.push((total_score, candidate, mirrored));
     }
 
     // Sort by total_score descending
     scored_candidates.sort_by(|a, b| {
-        b.0.total_score
-            .partial_cmp(&a.0.total_score)
+        b.0.partial_cmp(&a.0)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
     // Try each valid candidate (best first)
-    for (_score, candidate) in scored_candidates {
-        // Make a new placement list with this candidate
+    for (_score, candidate, mirrored) in scored_candidates {
+        // Make a new placement list with this candidate (and its mirror image)
         let mut new_already_placed = already_placed.clone();
         new_already_placed.push(candidate);
+        new_already_placed.extend(mirrored);
 
         // Recursively try to place remaining rooms
         let result = solve_recursive(
             remaining_rooms,
+            room_requirements,
             new_already_placed,
             boundary_width,
             boundary_height,
+            config,
+            room_zones,
         );
 
         // If successful, return the solution
@@ -143,6 +517,25 @@ fn solve_recursive(
 mod tests {
     use super::*;
     use crate::geometry::Rectangle;
+    use crate::scoring::{Objective, ScoringWeights};
+    use crate::walls::WallThickness;
+    use crate::types::{
+        AdjacencyRelation, AdjacencyStrength, Alignment, AlignmentGroup, DistanceConstraint, DistanceMetric, Entrance,
+        MirrorAxis, MirrorGroup, ZoneContiguity,
+    };
+
+    fn requirement(id: &str, min_area: f64, adjacent_to: &[&str]) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn placed(solution: &LayoutSolution, id: &str) -> Rectangle {
+        Rectangle::from_room(solution.rooms.iter().find(|r| r.id == id).unwrap())
+    }
 
     // Test 1: test_solve_simple_layout_two_rooms
     #[test]
@@ -153,6 +546,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         let room2 = RoomRequirement {
@@ -161,6 +555,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         let result = solve_layout(
@@ -185,6 +580,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         let room2 = RoomRequirement {
@@ -193,6 +589,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         // Boundary: 10.0 × 10.0 (total area = 100, but can't fit both efficiently)
@@ -211,6 +608,7 @@ mod tests {
             adjacent_to: vec!["room2".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         // Room2: min_area = 9.0, no requirements
@@ -220,6 +618,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         // Boundary: 10.0 × 10.0
@@ -252,6 +651,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: true,
+            ..Default::default()
         };
 
         // Boundary: 10.0 × 10.0
@@ -280,6 +680,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         // Room2: min_area = 9.0, adjacent_to = ["simple"], has_exterior_wall = true (ID: "complex")
@@ -289,6 +690,7 @@ mod tests {
             adjacent_to: vec!["simple".to_string()],
             not_adjacent_to: vec![],
             has_exterior_wall: true,
+            ..Default::default()
         };
 
         // Boundary: 10.0 × 10.0
@@ -313,6 +715,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: true, // Should get bonus points
+            ..Default::default()
         };
 
         let room2 = RoomRequirement {
@@ -321,6 +724,7 @@ mod tests {
             adjacent_to: vec!["room1".to_string()], // Should get adjacency bonus
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         let result = solve_layout(vec![room1, room2], 10.0, 10.0);
@@ -347,6 +751,7 @@ mod tests {
             adjacent_to: vec!["kitchen".to_string()],
             not_adjacent_to: vec!["bathroom".to_string()],
             has_exterior_wall: true,
+            ..Default::default()
         };
 
         // Kitchen - adjacent to living
@@ -356,6 +761,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         // Bathroom - cannot be adjacent to living
@@ -365,6 +771,7 @@ mod tests {
             adjacent_to: vec![],
             not_adjacent_to: vec![],
             has_exterior_wall: false,
+            ..Default::default()
         };
 
         let result = solve_layout(
@@ -414,4 +821,694 @@ mod tests {
             bathroom_room.x, bathroom_room.y, bathroom_room.width, bathroom_room.height
         );
     }
+
+    // Test 8: test_solve_rejects_invalid_requirements
+    #[test]
+    fn test_solve_rejects_invalid_requirements() {
+        let room1 = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 9.0,
+            adjacent_to: vec!["missing".to_string()],
+            not_adjacent_to: vec![],
+            has_exterior_wall: false,
+            ..Default::default()
+        };
+
+        let result = solve_layout(vec![room1], 10.0, 10.0);
+
+        match result {
+            Err(SolverError::InvalidRequirements(errors)) => assert_eq!(errors.len(), 1),
+            _ => panic!("Expected InvalidRequirements error"),
+        }
+    }
+
+    // Test 9: test_solve_reports_infeasible_program_without_search
+    #[test]
+    fn test_solve_reports_infeasible_program_without_search() {
+        let room1 = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 60.0,
+            adjacent_to: vec![],
+            not_adjacent_to: vec![],
+            has_exterior_wall: false,
+            ..Default::default()
+        };
+
+        let room2 = RoomRequirement {
+            id: "room2".to_string(),
+            min_area: 60.0,
+            adjacent_to: vec![],
+            not_adjacent_to: vec![],
+            has_exterior_wall: false,
+            ..Default::default()
+        };
+
+        let result = solve_layout(vec![room1, room2], 10.0, 10.0);
+
+        assert!(matches!(
+            result,
+            Err(SolverError::Infeasible(Infeasibility::TotalAreaExceedsBoundary { .. }))
+        ));
+    }
+
+    // Test 10: test_solve_with_custom_objective
+    #[test]
+    fn test_solve_with_custom_objective() {
+        // Objective that rewards placing rooms further right
+        struct PreferEast;
+
+        impl Objective for PreferEast {
+            fn name(&self) -> &str {
+                "prefer_east"
+            }
+
+            fn score(&self, placed_room: &Room, _: &RoomRequirement, _: &[Room], _: f64, _: f64) -> f64 {
+                placed_room.x
+            }
+        }
+
+        let room1 = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 9.0,
+            ..Default::default()
+        };
+
+        let config = SolverConfig::default().with_objective(PreferEast);
+        let solution = solve_layout_with_config(vec![room1], 10.0, 10.0, &config).unwrap();
+
+        let placed = &solution.rooms[0];
+        assert_eq!(placed.x + placed.width, 10.0, "Room should be pushed to the east wall");
+    }
+
+    // Test 11: test_solve_with_custom_weights_changes_total_score
+    #[test]
+    fn test_solve_with_custom_weights_changes_total_score() {
+        let room1 = RoomRequirement {
+            id: "room1".to_string(),
+            min_area: 9.0,
+            ..Default::default()
+        };
+
+        let config = SolverConfig {
+            weights: ScoringWeights {
+                hard_constraints: 100.0,
+                validity_bonus: 0.0,
+                exterior_wall_bonus: 0.0,
+                space_efficiency: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let solution = solve_layout_with_config(vec![room1], 10.0, 10.0, &config).unwrap();
+
+        assert_eq!(solution.total_score, 100.0);
+    }
+
+    // Test 12: test_solve_respects_hard_distance_constraint
+    #[test]
+    fn test_solve_respects_hard_distance_constraint() {
+        let living = RoomRequirement {
+            id: "living".to_string(),
+            min_area: 16.0,
+            has_exterior_wall: true,
+            ..Default::default()
+        };
+
+        let master = RoomRequirement {
+            id: "master".to_string(),
+            min_area: 9.0,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "living".to_string(),
+                min_distance: Some(6.0),
+                max_distance: None,
+                metric: DistanceMetric::CenterToCenter,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+
+        let solution = solve_layout(vec![living, master], 12.0, 12.0).unwrap();
+
+        let living_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "living").unwrap());
+        let master_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "master").unwrap());
+        assert!(living_rect.center_distance(&master_rect) >= 6.0);
+    }
+
+    // Test 13: test_solve_aligns_bedroom_depths
+    #[test]
+    fn test_solve_aligns_bedroom_depths() {
+        let bedroom = |id: &str, min_area: f64| RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            ..Default::default()
+        };
+        let config = SolverConfig::default().with_alignment_group(AlignmentGroup {
+            room_ids: vec!["bedroom1".to_string(), "bedroom2".to_string(), "bedroom3".to_string()],
+            alignment: Alignment::SameDepth,
+            hard: true,
+            weight: 0.0,
+        });
+
+        let rooms = vec![bedroom("bedroom1", 12.0), bedroom("bedroom2", 9.0), bedroom("bedroom3", 10.5)];
+        let solution = solve_layout_with_config(rooms, 12.0, 8.0, &config).unwrap();
+
+        let depths: Vec<f64> = solution.rooms.iter().map(|r| r.height).collect();
+        assert!(depths.iter().all(|depth| *depth == depths[0]), "depths differ: {:?}", depths);
+    }
+
+    // Test 14: test_solve_rejects_group_with_unknown_room
+    #[test]
+    fn test_solve_rejects_group_with_unknown_room() {
+        let rooms = vec![RoomRequirement { id: "bath".to_string(), min_area: 4.0, ..Default::default() }];
+        let config = SolverConfig::default().with_alignment_group(AlignmentGroup {
+            room_ids: vec!["bath".to_string(), "ensuite".to_string()],
+            alignment: Alignment::Left,
+            hard: true,
+            weight: 0.0,
+        });
+
+        let result = solve_layout_with_config(rooms, 10.0, 10.0, &config);
+        assert!(matches!(
+            result,
+            Err(SolverError::InvalidRequirements(errors))
+                if errors == vec![ValidationError::UnknownGroupMember { referenced_id: "ensuite".to_string() }]
+        ));
+    }
+
+    // Test 15: test_solve_mirrors_unit_across_vertical_axis
+    #[test]
+    fn test_solve_mirrors_unit_across_vertical_axis() {
+        let room = |id: &str, min_area: f64, adjacent_to: &[&str]| RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        let rooms = vec![
+            room("living_a", 16.0, &[]),
+            room("bath_a", 4.0, &["living_a"]),
+            room("living_b", 16.0, &[]),
+            room("bath_b", 4.0, &["living_b"]),
+        ];
+        let config = SolverConfig::default().with_mirror_group(MirrorGroup {
+            room_ids: vec!["living_a".to_string(), "bath_a".to_string()],
+            mirrored_ids: vec!["living_b".to_string(), "bath_b".to_string()],
+            axis: MirrorAxis::Vertical,
+        });
+
+        let solution = solve_layout_with_config(rooms, 12.0, 6.0, &config).unwrap();
+        assert_eq!(solution.rooms.len(), 4);
+
+        for (source_id, mirrored_id) in [("living_a", "living_b"), ("bath_a", "bath_b")] {
+            let source = solution.rooms.iter().find(|r| r.id == source_id).unwrap();
+            let mirrored = solution.rooms.iter().find(|r| r.id == mirrored_id).unwrap();
+            assert_eq!(mirrored.x, 12.0 - source.x - source.width);
+            assert_eq!((mirrored.y, mirrored.width, mirrored.height), (source.y, source.width, source.height));
+            assert!(solution.mirrored_rooms.contains(&MirroredRoom {
+                room_id: mirrored_id.to_string(),
+                source_id: source_id.to_string(),
+            }));
+        }
+    }
+
+    // Test 16: test_solve_keeps_private_zone_contiguous
+    #[test]
+    fn test_solve_keeps_private_zone_contiguous() {
+        let room = |id: &str, min_area: f64, zone: Zone| RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            zone: Some(zone),
+            ..Default::default()
+        };
+        let rooms = vec![
+            room("living", 16.0, Zone::Public),
+            room("bed1", 9.0, Zone::Private),
+            room("bed2", 9.0, Zone::Private),
+            room("bed3", 9.0, Zone::Private),
+        ];
+        let room_zones = crate::zoning::room_zones(&rooms);
+        let config = SolverConfig {
+            zone_contiguity: vec![ZoneContiguity { zone: Zone::Private, hard: true, weight: 0.0 }],
+            ..Default::default()
+        };
+
+        let solution = solve_layout_with_config(rooms, 10.0, 10.0, &config).unwrap();
+        assert!(crate::zoning::is_zone_contiguous(Zone::Private, &solution.rooms, &room_zones));
+    }
+
+    // Test 17: test_solve_places_counts_and_drops_optional_rooms
+    #[test]
+    fn test_solve_places_counts_and_drops_optional_rooms() {
+        let rooms = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 20.0, ..Default::default() },
+            RoomRequirement {
+                id: "bedroom".to_string(),
+                min_area: 9.0,
+                min_count: Some(2),
+                max_count: Some(3),
+                ..Default::default()
+            },
+            // Too big for what is left once everything else is placed
+            RoomRequirement {
+                id: "study".to_string(),
+                min_area: 40.0,
+                optional: true,
+                priority: 5,
+                ..Default::default()
+            },
+        ];
+
+        let solution = solve_layout(rooms, 8.0, 8.0).unwrap();
+
+        let mut placed_ids: Vec<&str> = solution.rooms.iter().map(|r| r.id.as_str()).collect();
+        placed_ids.sort();
+        assert_eq!(placed_ids, vec!["bedroom_1", "bedroom_2", "bedroom_3", "living"]);
+        assert_eq!(solution.dropped_rooms, vec!["study".to_string()]);
+    }
+
+    // Test 18: test_solve_fill_mode_covers_boundary
+    #[test]
+    fn test_solve_fill_mode_covers_boundary() {
+        let rooms = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 16.0, max_area: Some(30.0), ..Default::default() },
+            RoomRequirement { id: "bedroom".to_string(), min_area: 9.0, max_area: Some(12.0), ..Default::default() },
+        ];
+        let config = SolverConfig { fill: true, ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms, 8.0, 6.0, &config).unwrap();
+
+        let room_area: f64 = solution.rooms.iter().map(|r| r.width * r.height).sum();
+        let circulation_area: f64 = solution.circulation.iter().map(|r| r.width * r.height).sum();
+        assert_eq!(room_area + circulation_area, 48.0);
+        assert!(room_area > 25.0);
+        assert_eq!(solution.net_to_gross, room_area / 48.0);
+        for room in solution.rooms.iter() {
+            assert!(room.width * room.height <= 30.0);
+        }
+    }
+
+    // Test 19: test_solution_reports_free_space
+    #[test]
+    fn test_solution_reports_free_space() {
+        let rooms = vec![RoomRequirement { id: "living".to_string(), min_area: 16.0, ..Default::default() }];
+
+        let solution = solve_layout(rooms, 8.0, 8.0).unwrap();
+
+        assert_eq!(solution.free_space.total_area, 64.0 - 16.0);
+        let largest = solution.free_space.largest.unwrap();
+        assert!(largest.width * largest.height >= 32.0);
+        assert!(solution.free_space.rectangles.iter().all(|r| r.touching_room_ids == vec!["living".to_string()]));
+    }
+
+    // Test 20: test_solve_with_corridors_reaches_every_room
+    #[test]
+    fn test_solve_with_corridors_reaches_every_room() {
+        let rooms = vec![
+            RoomRequirement { id: "bed1".to_string(), min_area: 9.0, ..Default::default() },
+            RoomRequirement { id: "bed2".to_string(), min_area: 9.0, ..Default::default() },
+            RoomRequirement { id: "bath".to_string(), min_area: 4.0, ..Default::default() },
+        ];
+        let entrances = vec![Entrance { x: 5.0, y: 8.0 }];
+        let config = SolverConfig { entrances: entrances.clone(), corridor_width: Some(1.0), ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms, 10.0, 8.0, &config).unwrap();
+
+        let plan = generate_corridors(&solution.rooms, &[], &entrances, 1.0, 10.0, 8.0);
+        assert!(plan.unreachable_room_ids.is_empty());
+        assert!(solution.circulation.iter().all(|c| c.id.starts_with("corridor_")));
+        for corridor in solution.circulation.iter() {
+            let rect = Rectangle::from_room(corridor);
+            assert!(solution.rooms.iter().all(|r| !rect.overlaps_with(&Rectangle::from_room(r))));
+        }
+    }
+
+    // Test 21: test_solve_places_entrance_room_and_keeps_rooms_reachable
+    #[test]
+    fn test_solve_places_entrance_room_and_keeps_rooms_reachable() {
+        let rooms = vec![
+            RoomRequirement { id: "foyer".to_string(), min_area: 4.0, contains_entrance: true, ..Default::default() },
+            RoomRequirement { id: "living".to_string(), min_area: 16.0, ..Default::default() },
+            RoomRequirement {
+                id: "bath".to_string(),
+                min_area: 4.0,
+                relations: vec![AdjacencyRelation {
+                    room_id: "living".to_string(),
+                    strength: AdjacencyStrength::Avoid,
+                    weight: 3.0,
+                }],
+                ..Default::default()
+            },
+        ];
+        let config = SolverConfig {
+            entrances: vec![Entrance { x: 8.0, y: 3.0 }],
+            require_reachability: true,
+            ..Default::default()
+        };
+
+        let solution = solve_layout_with_config(rooms, 8.0, 6.0, &config).unwrap();
+
+        let foyer = solution.rooms.iter().find(|r| r.id == "foyer").unwrap();
+        assert!(Rectangle::from_room(foyer).contains_point(8.0, 3.0));
+        assert!(solution.unreachable_rooms.is_empty());
+    }
+
+    // Test 22: test_entrance_off_boundary_rejected
+    #[test]
+    fn test_entrance_off_boundary_rejected() {
+        let rooms = vec![RoomRequirement { id: "living".to_string(), min_area: 16.0, ..Default::default() }];
+        let config = SolverConfig { entrances: vec![Entrance { x: 4.0, y: 3.0 }], ..Default::default() };
+
+        let result = solve_layout_with_config(rooms, 8.0, 6.0, &config);
+        assert!(matches!(
+            result,
+            Err(SolverError::InvalidRequirements(errors))
+                if errors == vec![ValidationError::EntranceOffBoundary { x: 4.0, y: 3.0 }]
+        ));
+    }
+
+    // Test 23: test_solution_has_door_per_required_connection
+    #[test]
+    fn test_solution_has_door_per_required_connection() {
+        let rooms = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 16.0, adjacent_to: vec!["kitchen".to_string()], ..Default::default() },
+            RoomRequirement { id: "kitchen".to_string(), min_area: 9.0, adjacent_to: vec!["living".to_string()], ..Default::default() },
+        ];
+
+        let solution = solve_layout(rooms, 10.0, 8.0).unwrap();
+
+        assert_eq!(solution.doors.len(), 1);
+        assert!(solution.blocked_doors.is_empty());
+        let living = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "living").unwrap());
+        let kitchen = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "kitchen").unwrap());
+        let door = &solution.doors[0].segment;
+        assert!(living.contains_point(door.x1, door.y1) && kitchen.contains_point(door.x2, door.y2));
+    }
+
+    // Test 24: test_solve_with_walls_checks_net_area
+    #[test]
+    fn test_solve_with_walls_checks_net_area() {
+        let rooms = vec![
+            RoomRequirement { id: "living".to_string(), min_area: 16.0, adjacent_to: vec!["kitchen".to_string()], ..Default::default() },
+            RoomRequirement { id: "kitchen".to_string(), min_area: 9.0, ..Default::default() },
+        ];
+        let config = SolverConfig { walls: Some(WallThickness::default()), ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms, 12.0, 8.0, &config).unwrap();
+
+        for (room, net) in solution.rooms.iter().zip(solution.net_rooms.iter()) {
+            let min_area = if room.id == "living" { 16.0 } else { 9.0 };
+            assert!(net.width * net.height >= min_area);
+            assert!(net.width < room.width && net.height < room.height);
+        }
+        // Adjacent rooms are one interior wall apart
+        let net = |id: &str| Rectangle::from_room(solution.net_rooms.iter().find(|r| r.id == id).unwrap());
+        let (living, kitchen) = (net("living"), net("kitchen"));
+        assert!((living.edge_distance(&kitchen) - 0.1).abs() < 1e-9);
+    }
+
+    // Test 25: test_rectangular_dual_strategy_realises_every_adjacency
+    #[test]
+    fn test_rectangular_dual_strategy_realises_every_adjacency() {
+        // The hall opens onto every room and the rooms form a ring around it
+        let mut rooms = vec![
+            requirement("hall", 6.0, &["living", "kitchen", "dining", "bed", "bath"]),
+            requirement("living", 16.0, &["dining", "bed"]),
+            requirement("dining", 9.0, &["kitchen"]),
+            requirement("kitchen", 9.0, &["bath"]),
+            requirement("bed", 12.0, &["bath"]),
+            requirement("bath", 4.0, &[]),
+        ];
+        rooms[1].has_exterior_wall = true;
+        let config = SolverConfig { strategy: SolverStrategy::RectangularDual, ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms.clone(), 10.0, 8.0, &config).unwrap();
+
+        for room_req in rooms.iter() {
+            let rect = placed(&solution, &room_req.id);
+            assert!(rect.width * rect.height >= room_req.min_area);
+            assert!(room_req.adjacent_to.iter().all(|other| rect.is_adjacent_to(&placed(&solution, other))));
+        }
+        assert!(placed(&solution, "living").touches_exterior_wall(10.0, 8.0));
+        // Gap-free: the rooms cover the whole boundary
+        let total: f64 = solution.rooms.iter().map(|r| r.width * r.height).sum();
+        assert!((total - 80.0).abs() < 1e-9);
+    }
+
+    // Test 26: test_slicing_tree_strategy_fills_boundary
+    #[test]
+    fn test_slicing_tree_strategy_fills_boundary() {
+        // Far less area is asked for than the boundary has, yet a slicing floorplan leaves no gaps
+        let rooms = vec![
+            requirement("living", 12.0, &["kitchen"]),
+            requirement("kitchen", 8.0, &[]),
+            requirement("bed", 10.0, &[]),
+            requirement("bath", 4.0, &[]),
+        ];
+        let config = SolverConfig { strategy: SolverStrategy::SlicingTree, ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms.clone(), 9.0, 6.0, &config).unwrap();
+
+        let total: f64 = solution.rooms.iter().map(|r| r.width * r.height).sum();
+        assert!((total - 54.0).abs() < 1e-9);
+        assert!(placed(&solution, "living").is_adjacent_to(&placed(&solution, "kitchen")));
+
+        // The first cut runs right across the boundary, with every room on one side of it
+        let rects: Vec<Rectangle> = solution.rooms.iter().map(Rectangle::from_room).collect();
+        let vertical_cut = rects.iter().map(|r| r.x).filter(|&x| x > 0.0).any(|x| {
+            rects.iter().all(|r| r.x + r.width <= x || r.x >= x)
+        });
+        let horizontal_cut = rects.iter().map(|r| r.y).filter(|&y| y > 0.0).any(|y| {
+            rects.iter().all(|r| r.y + r.height <= y || r.y >= y)
+        });
+        assert!(vertical_cut || horizontal_cut);
+    }
+
+    // Test 27: test_sequence_pair_strategy_packs_rooms_up_and_left
+    #[test]
+    fn test_sequence_pair_strategy_packs_rooms_up_and_left() {
+        let rooms = vec![
+            requirement("living", 16.0, &["kitchen"]),
+            requirement("kitchen", 8.0, &[]),
+            requirement("bed", 12.0, &[]),
+            requirement("bath", 4.0, &[]),
+        ];
+        let config = SolverConfig { strategy: SolverStrategy::SequencePair, ..Default::default() };
+
+        let solution = solve_layout_with_config(rooms.clone(), 12.0, 8.0, &config).unwrap();
+
+        // Every room is pushed against the boundary or another room's far edge, on both axes
+        let rects: Vec<Rectangle> = solution.rooms.iter().map(Rectangle::from_room).collect();
+        for (i, rect) in rects.iter().enumerate() {
+            assert!(rect.x == 0.0 || rects.iter().any(|other| other.x + other.width == rect.x));
+            assert!(rect.y == 0.0 || rects.iter().any(|other| other.y + other.height == rect.y));
+            assert!(rects[i + 1..].iter().all(|other| !rect.overlaps_with(other)));
+        }
+        assert!(placed(&solution, "living").is_adjacent_to(&placed(&solution, "kitchen")));
+    }
+
+    // Test 28: test_solver_errors_serialize_with_kind
+    #[test]
+    fn test_solver_errors_serialize_with_kind() {
+        let rooms = vec![RoomRequirement { id: "hall".to_string(), min_area: -1.0, ..Default::default() }];
+
+        let Err(error) = solve_layout(rooms, 10.0, 10.0) else { panic!("expected a validation error") };
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["kind"], "invalid_requirements");
+        assert_eq!(json["details"][0]["kind"], "non_positive_area");
+        assert!(error.to_string().starts_with("Invalid requirements: "));
+    }
+
+    // Test 29: test_hard_distance_checked_when_owner_placed_first
+    #[test]
+    fn test_hard_distance_checked_when_owner_placed_first() {
+        // The exterior wall requirement orders master ahead of living
+        let master = RoomRequirement {
+            id: "master".to_string(),
+            min_area: 9.0,
+            has_exterior_wall: true,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "living".to_string(),
+                min_distance: Some(6.0),
+                max_distance: None,
+                metric: DistanceMetric::CenterToCenter,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+        let living = RoomRequirement { id: "living".to_string(), min_area: 16.0, ..Default::default() };
+        let ordered = order_rooms_by_constraints(vec![living.clone(), master.clone()]);
+        assert_eq!(ordered[0].id, "master");
+
+        let solution = solve_layout(vec![living, master], 12.0, 12.0).unwrap();
+
+        let living_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "living").unwrap());
+        let master_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "master").unwrap());
+        assert!(living_rect.center_distance(&master_rect) >= 6.0);
+    }
+
+    // Test 30: test_hard_position_checked_when_owner_placed_first
+    #[test]
+    fn test_hard_position_checked_when_owner_placed_first() {
+        use crate::types::{PositionConstraint, RelativePosition};
+
+        // The exterior wall requirement orders bed ahead of living
+        let bed = RoomRequirement {
+            id: "bed".to_string(),
+            min_area: 9.0,
+            has_exterior_wall: true,
+            position_constraints: vec![PositionConstraint {
+                room_id: "living".to_string(),
+                position: RelativePosition::Below,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+        let living = RoomRequirement { id: "living".to_string(), min_area: 16.0, ..Default::default() };
+        let ordered = order_rooms_by_constraints(vec![living.clone(), bed.clone()]);
+        assert_eq!(ordered[0].id, "bed");
+
+        let solution = solve_layout(vec![living, bed], 10.0, 10.0).unwrap();
+
+        let bed_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "bed").unwrap());
+        let living_rect = Rectangle::from_room(solution.rooms.iter().find(|r| r.id == "living").unwrap());
+        assert!(bed_rect.is_positioned(RelativePosition::Below, &living_rect));
+    }
+
+    // Test 31: test_unreachable_corridor_layouts_pruned
+    #[test]
+    fn test_unreachable_corridor_layouts_pruned() {
+        // Square 2 m rooms cannot open onto a 3 m corridor; only rooms on the entrance are reached
+        let room = |id: &str| RoomRequirement {
+            id: id.to_string(),
+            min_area: 4.0,
+            max_aspect: Some(1.0),
+            ..Default::default()
+        };
+        let rooms = vec![room("bed"), room("bath"), room("store")];
+        let config = SolverConfig {
+            entrances: vec![Entrance { x: 5.0, y: 10.0 }],
+            corridor_width: Some(3.0),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = solve_layout_with_config(rooms, 10.0, 10.0, &config);
+
+        assert!(matches!(result, Err(SolverError::NoSolutionFound(_))));
+        assert!(started.elapsed().as_secs() < 5);
+    }
+
+    // Test 32: test_unreachable_rooms_pruned
+    #[test]
+    fn test_unreachable_rooms_pruned() {
+        // Rooms that avoid each other can only be reached by touching the entrance, and at most two can
+        let room = |id: &str, others: [&str; 2]| RoomRequirement {
+            id: id.to_string(),
+            min_area: 4.0,
+            relations: others
+                .iter()
+                .map(|other| AdjacencyRelation {
+                    room_id: other.to_string(),
+                    strength: AdjacencyStrength::Avoid,
+                    weight: 1.0,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let rooms =
+            vec![room("bed", ["bath", "store"]), room("bath", ["bed", "store"]), room("store", ["bed", "bath"])];
+        let config = SolverConfig {
+            entrances: vec![Entrance { x: 5.0, y: 10.0 }],
+            require_reachability: true,
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = solve_layout_with_config(rooms, 10.0, 10.0, &config);
+
+        assert!(matches!(result, Err(SolverError::NoSolutionFound(_))));
+        assert!(started.elapsed().as_secs() < 5);
+    }
+
+    // Test 33: test_hard_adjacency_checked_when_owner_placed_first
+    #[test]
+    fn test_hard_adjacency_checked_when_owner_placed_first() {
+        // The relations order the bath first, so its relations are checked when the kitchen and the bed are placed
+        let bath = RoomRequirement {
+            id: "bath".to_string(),
+            min_area: 4.0,
+            adjacent_to: vec!["bed".to_string()],
+            not_adjacent_to: vec!["kitchen".to_string()],
+            ..Default::default()
+        };
+        let bed = requirement("bed", 9.0, &[]);
+        let kitchen = requirement("kitchen", 9.0, &[]);
+        let ordered = order_rooms_by_constraints(vec![kitchen.clone(), bed.clone(), bath.clone()]);
+        assert_eq!(ordered[0].id, "bath");
+
+        let solution = solve_layout(vec![kitchen, bed, bath], 12.0, 12.0).unwrap();
+
+        assert!(placed(&solution, "bath").is_adjacent_to(&placed(&solution, "bed")));
+        assert!(!placed(&solution, "bath").is_adjacent_to(&placed(&solution, "kitchen")));
+    }
+
+    // Test 34: test_hard_walking_distance_checked_on_complete_layout
+    #[test]
+    fn test_hard_walking_distance_checked_on_complete_layout() {
+        use crate::distance::walking_route;
+
+        // The bath may not open onto the bed, so the walk has to pass through the living room
+        let bed = RoomRequirement {
+            id: "bed".to_string(),
+            min_area: 9.0,
+            has_exterior_wall: true,
+            distance_constraints: vec![DistanceConstraint {
+                room_id: "bath".to_string(),
+                min_distance: None,
+                max_distance: Some(7.0),
+                metric: DistanceMetric::WalkingPath,
+                hard: true,
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+        let mut bath = requirement("bath", 4.0, &[]);
+        bath.not_adjacent_to = vec!["bed".to_string()];
+        let rooms = vec![bed, requirement("living", 16.0, &[]), bath];
+
+        let solution = solve_layout(rooms, 12.0, 12.0).unwrap();
+
+        let find = |id: &str| solution.rooms.iter().find(|r| r.id == id).unwrap();
+        let walk = walking_route(find("bed"), find("bath"), &solution.rooms);
+        assert!(walk.is_some_and(|distance| distance <= 7.0), "walk {:?}", walk);
+    }
+
+    // Test 35: test_corridor_search_agrees_with_reported_reachability
+    #[test]
+    fn test_corridor_search_agrees_with_reported_reachability() {
+        // The rooms fill the boundary, so the living room can only be entered through the foyer it avoids
+        let foyer = RoomRequirement { contains_entrance: true, ..requirement("foyer", 9.0, &[]) };
+        let living = RoomRequirement {
+            relations: vec![AdjacencyRelation {
+                room_id: "foyer".to_string(),
+                strength: AdjacencyStrength::Avoid,
+                weight: 1.0,
+            }],
+            ..requirement("living", 9.0, &[])
+        };
+        let config = SolverConfig {
+            entrances: vec![Entrance { x: 1.5, y: 0.0 }],
+            corridor_width: Some(1.0),
+            ..Default::default()
+        };
+
+        let result = solve_layout_with_config(vec![foyer, living], 6.0, 3.0, &config);
+
+        // The search must not accept a layout that the solution then reports as unreachable
+        assert!(result.is_err(), "unreachable {:?}", result.map(|solution| solution.unreachable_rooms));
+    }
 }
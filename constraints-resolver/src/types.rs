@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// A room with a position and size.
 #[derive(Debug, Clone)]
 pub struct Room {
@@ -8,11 +10,387 @@ pub struct Room {
     pub height: f64,
 }
 
+/// How strongly two rooms should (or should not) share a wall.
+///
+/// Only `Required` and `Forbidden` are hard constraints; the other levels
+/// add or subtract their relation weight from the soft preference score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjacencyStrength {
+    Required,
+    StronglyPreferred,
+    Preferred,
+    Avoid,
+    Forbidden,
+}
+
+impl AdjacencyStrength {
+    /// Weight used when a relation does not specify one.
+    pub fn default_weight(&self) -> f64 {
+        match self {
+            AdjacencyStrength::Required => 5.0,
+            AdjacencyStrength::StronglyPreferred => 4.0,
+            AdjacencyStrength::Preferred => 2.0,
+            AdjacencyStrength::Avoid => 3.0,
+            AdjacencyStrength::Forbidden => 0.0,
+        }
+    }
+}
+
+/// A weighted adjacency relation from one room to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdjacencyRelation {
+    pub room_id: String,
+    pub strength: AdjacencyStrength,
+    pub weight: f64,
+}
+
+/// How the distance between two rooms is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMetric {
+    /// Straight line between room centres.
+    #[default]
+    CenterToCenter,
+    /// Shortest gap between the room outlines (0 when they touch).
+    EdgeToEdge,
+    /// Walking route from centre to centre through shared walls.
+    WalkingPath,
+}
+
+fn default_constraint_weight() -> f64 {
+    5.0
+}
+
+/// A minimum and/or maximum distance from one room to another.
+///
+/// Hard constraints reject placements outside the range; soft ones add
+/// `weight` to the soft preference score when the range is met.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistanceConstraint {
+    pub room_id: String,
+    #[serde(default)]
+    pub min_distance: Option<f64>,
+    #[serde(default)]
+    pub max_distance: Option<f64>,
+    #[serde(default)]
+    pub metric: DistanceMetric,
+    #[serde(default)]
+    pub hard: bool,
+    #[serde(default = "default_constraint_weight")]
+    pub weight: f64,
+}
+
+impl DistanceConstraint {
+    /// Whether a measured distance falls within the constraint's range.
+    pub fn is_satisfied_by(&self, distance: f64) -> bool {
+        self.min_distance.is_none_or(|min_distance| distance >= min_distance)
+            && self.max_distance.is_none_or(|max_distance| distance <= max_distance)
+    }
+}
+
+/// A side of the layout boundary in plan coordinates (y grows downward, so `Top` is y = 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundarySide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl BoundarySide {
+    /// All four sides, in plan order.
+    pub const ALL: [BoundarySide; 4] = [BoundarySide::Top, BoundarySide::Right, BoundarySide::Bottom, BoundarySide::Left];
+
+    /// Compass direction the side faces.
+    ///
+    /// `north_angle` is the clockwise angle in degrees from the plan's up
+    /// direction (the `Top` side) to true north; 0.0 means the top faces north.
+    pub fn orientation(&self, north_angle: f64) -> Orientation {
+        let plan_bearing = match self {
+            BoundarySide::Top => 0.0,
+            BoundarySide::Right => 90.0,
+            BoundarySide::Bottom => 180.0,
+            BoundarySide::Left => 270.0,
+        };
+        let bearing = (plan_bearing - north_angle).rem_euclid(360.0);
+
+        match ((bearing / 90.0).round() as i64).rem_euclid(4) {
+            0 => Orientation::North,
+            1 => Orientation::East,
+            2 => Orientation::South,
+            _ => Orientation::West,
+        }
+    }
+}
+
+/// A compass direction a facade faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Orientation {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// Where a room must sit relative to another room, in plan coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelativePosition {
+    /// Entirely above the other room (smaller y).
+    Above,
+    /// Entirely below the other room (larger y).
+    Below,
+    /// Entirely left of the other room (smaller x).
+    LeftOf,
+    /// Entirely right of the other room (larger x).
+    RightOf,
+}
+
+/// A relative position from one room to another.
+///
+/// Hard constraints reject placements that break it; soft ones add
+/// `weight` to the soft preference score when it holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionConstraint {
+    pub room_id: String,
+    pub position: RelativePosition,
+    #[serde(default)]
+    pub hard: bool,
+    #[serde(default = "default_constraint_weight")]
+    pub weight: f64,
+}
+
+/// A dimension or edge shared by every room in an alignment group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    /// Same extent along x.
+    SameWidth,
+    /// Same extent along y.
+    SameDepth,
+    /// Top edges on the same line (same y).
+    Top,
+    /// Bottom edges on the same line.
+    Bottom,
+    /// Left edges on the same line (same x).
+    Left,
+    /// Right edges on the same line.
+    Right,
+}
+
+/// A group of rooms that share a dimension or an aligned edge.
+///
+/// Hard groups reject placements that break the alignment with any member
+/// already placed; soft ones add `weight` to the soft preference score for
+/// each placed member the room lines up with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentGroup {
+    pub room_ids: Vec<String>,
+    pub alignment: Alignment,
+    #[serde(default)]
+    pub hard: bool,
+    #[serde(default = "default_constraint_weight")]
+    pub weight: f64,
+}
+
+impl AlignmentGroup {
+    /// Whether the room belongs to the group.
+    pub fn contains(&self, room_id: &str) -> bool {
+        self.room_ids.iter().any(|id| id == room_id)
+    }
+}
+
+/// A centre line of the boundary that mirror groups reflect across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorAxis {
+    /// The vertical line x = width / 2; left and right swap.
+    Vertical,
+    /// The horizontal line y = height / 2; top and bottom swap.
+    Horizontal,
+}
+
+/// Two groups of rooms that are mirror images across a boundary axis.
+///
+/// `mirrored_ids[i]` is the mirror image of `room_ids[i]`. Only the rooms in
+/// `room_ids` are searched; each counterpart is derived from its source as
+/// soon as the source is placed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MirrorGroup {
+    pub room_ids: Vec<String>,
+    pub mirrored_ids: Vec<String>,
+    pub axis: MirrorAxis,
+}
+
+impl MirrorGroup {
+    /// The counterpart derived from a source room, if the room is a source in this group.
+    pub fn counterpart_of(&self, room_id: &str) -> Option<&str> {
+        let index = self.room_ids.iter().position(|id| id == room_id)?;
+        self.mirrored_ids.get(index).map(String::as_str)
+    }
+
+    /// The source room a derived room mirrors, if the room is derived in this group.
+    pub fn source_of(&self, room_id: &str) -> Option<&str> {
+        let index = self.mirrored_ids.iter().position(|id| id == room_id)?;
+        self.room_ids.get(index).map(String::as_str)
+    }
+}
+
+/// A functional zone of the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Zone {
+    /// Living and reception rooms, best kept near the entrance.
+    Public,
+    /// Bedrooms and studies, best kept deep in the plan.
+    Private,
+    /// Kitchens, utility and storage.
+    Service,
+}
+
+/// A constraint that keeps the rooms of one zone in a single cluster.
+///
+/// Hard constraints reject placements that split the zone; soft ones add
+/// `weight` to a placement's score when it joins the existing cluster.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneContiguity {
+    pub zone: Zone,
+    #[serde(default)]
+    pub hard: bool,
+    #[serde(default = "default_constraint_weight")]
+    pub weight: f64,
+}
+
+/// An entrance point on the boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Entrance {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Entrance {
+    /// Check if the entrance lies on the outline of the boundary.
+    pub fn is_on_perimeter(&self, boundary_width: f64, boundary_height: f64) -> bool {
+        let within = self.x >= 0.0 && self.x <= boundary_width && self.y >= 0.0 && self.y <= boundary_height;
+        let on_edge = self.x == 0.0 || self.x == boundary_width || self.y == 0.0 || self.y == boundary_height;
+        within && on_edge
+    }
+}
+
 /// A requirement for a room.
+///
+/// Size bounds are optional; `None` leaves that dimension unconstrained.
+/// Width is measured along x and depth along y. Aspect ratio is the longer
+/// side divided by the shorter side, so it is always at least 1.0.
+///
+/// `relations` carries weighted adjacency preferences in addition to the
+/// hard `adjacent_to`/`not_adjacent_to` lists; `distance_constraints` bound
+/// how far this room may be from others and `position_constraints` fix which
+/// side of them it sits on.
+///
+/// `exterior_sides` narrows `has_exterior_wall` to specific boundary sides:
+/// when non-empty the room must touch at least one of them, whether or not
+/// `has_exterior_wall` is set. `facade_orientations` and `min_facade_length`
+/// further require enough exterior wall facing the given compass directions
+/// (any direction when the list is empty). `glazing_ratio` asks for window
+/// area of at least that share of the floor area; windows go on walls
+/// facing `facade_orientations` first.
+///
+/// `zone` tags the room as public, private or service for zone contiguity
+/// and the entrance gradient. `contains_entrance` requires one of the
+/// layout's entrances to lie on the room's outline, as for a foyer.
+///
+/// `min_count`/`max_count` ask for several identical rooms (one of each by
+/// default); the solver expands them into instances before solving. Instances
+/// beyond `min_count`, and every instance of an `optional` room, are placed
+/// only if they fit, highest `priority` first.
+#[derive(Debug, Clone, Default)]
 pub struct RoomRequirement {
     pub id: String,
     pub min_area: f64,
     pub adjacent_to: Vec<String>,
     pub not_adjacent_to: Vec<String>,
-    pub has_exterior_wall: bool,   
+    pub has_exterior_wall: bool,
+    pub max_area: Option<f64>,
+    pub min_width: Option<f64>,
+    pub min_depth: Option<f64>,
+    pub min_aspect: Option<f64>,
+    pub max_aspect: Option<f64>,
+    pub relations: Vec<AdjacencyRelation>,
+    pub distance_constraints: Vec<DistanceConstraint>,
+    pub position_constraints: Vec<PositionConstraint>,
+    pub exterior_sides: Vec<BoundarySide>,
+    pub facade_orientations: Vec<Orientation>,
+    pub min_facade_length: Option<f64>,
+    pub glazing_ratio: Option<f64>,
+    pub zone: Option<Zone>,
+    pub contains_entrance: bool,
+    pub min_count: Option<usize>,
+    pub max_count: Option<usize>,
+    pub optional: bool,
+    pub priority: u32,
+}
+
+impl RoomRequirement {
+    /// Whether the room must touch the exterior wall on some side.
+    pub fn needs_exterior_wall(&self) -> bool {
+        self.has_exterior_wall
+            || !self.exterior_sides.is_empty()
+            || self.has_facade_requirement()
+            || self.contains_entrance
+    }
+
+    /// Number of instances that must be placed (0 for an optional room).
+    pub fn required_count(&self) -> usize {
+        if self.optional { 0 } else { self.min_count.unwrap_or(1) }
+    }
+
+    /// Total number of instances the solver tries to place.
+    pub fn instance_count(&self) -> usize {
+        self.max_count.unwrap_or_else(|| self.min_count.unwrap_or(1))
+    }
+
+    /// Whether the room constrains the direction or length of its facade.
+    pub fn has_facade_requirement(&self) -> bool {
+        !self.facade_orientations.is_empty() || self.min_facade_length.is_some()
+    }
+
+    /// All adjacency relations of this room.
+    ///
+    /// `adjacent_to` and `not_adjacent_to` are shorthand for `Required` and
+    /// `Forbidden` relations with default weights; they come first, followed
+    /// by the explicit `relations`.
+    pub fn adjacency_relations(&self) -> Vec<AdjacencyRelation> {
+        let shorthand = [
+            (AdjacencyStrength::Required, &self.adjacent_to),
+            (AdjacencyStrength::Forbidden, &self.not_adjacent_to),
+        ];
+
+        let mut relations = Vec::new();
+        for (strength, room_ids) in shorthand {
+            for room_id in room_ids {
+                relations.push(AdjacencyRelation {
+                    room_id: room_id.clone(),
+                    strength,
+                    weight: strength.default_weight(),
+                });
+            }
+        }
+        relations.extend(self.relations.iter().cloned());
+        relations
+    }
+
+    /// Ids of rooms related to this one with the given strength.
+    pub fn related_rooms(&self, strength: AdjacencyStrength) -> Vec<String> {
+        let mut room_ids: Vec<String> = Vec::new();
+        for relation in self.adjacency_relations() {
+            if relation.strength == strength && !room_ids.contains(&relation.room_id) {
+                room_ids.push(relation.room_id);
+            }
+        }
+        room_ids
+    }
 }
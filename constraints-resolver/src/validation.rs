@@ -0,0 +1,904 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SolverConfig, SolverStrategy};
+use crate::types::{AdjacencyStrength, Entrance, RoomRequirement};
+
+/// Which relationship list a room reference came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    AdjacentTo,
+    NotAdjacentTo,
+    Relations,
+    DistanceConstraints,
+    PositionConstraints,
+}
+
+impl fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelationKind::AdjacentTo => write!(f, "adjacent_to"),
+            RelationKind::NotAdjacentTo => write!(f, "not_adjacent_to"),
+            RelationKind::Relations => write!(f, "relations"),
+            RelationKind::DistanceConstraints => write!(f, "distance_constraints"),
+            RelationKind::PositionConstraints => write!(f, "position_constraints"),
+        }
+    }
+}
+
+/// An optional size bound on a room requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeBound {
+    MinArea,
+    MaxArea,
+    MinWidth,
+    MinDepth,
+    MinAspect,
+    MaxAspect,
+    MinFacadeLength,
+    GlazingRatio,
+}
+
+impl fmt::Display for SizeBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeBound::MinArea => write!(f, "min_area"),
+            SizeBound::MaxArea => write!(f, "max_area"),
+            SizeBound::MinWidth => write!(f, "min_width"),
+            SizeBound::MinDepth => write!(f, "min_depth"),
+            SizeBound::MinAspect => write!(f, "min_aspect"),
+            SizeBound::MaxAspect => write!(f, "max_aspect"),
+            SizeBound::MinFacadeLength => write!(f, "min_facade_length"),
+            SizeBound::GlazingRatio => write!(f, "glazing_ratio"),
+        }
+    }
+}
+
+/// A problem with the room requirements detected before solving.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationError {
+    /// Two or more requirements share the same id.
+    DuplicateId { room_id: String },
+    /// The minimum area is zero, negative or not a number.
+    NonPositiveArea { room_id: String, min_area: f64 },
+    /// A size bound is out of its valid range (non-positive, or an aspect below 1.0).
+    InvalidBound { room_id: String, bound: SizeBound, value: f64 },
+    /// A lower size bound exceeds the matching upper bound.
+    ConflictingBounds { room_id: String, lower: SizeBound, upper: SizeBound },
+    /// A relationship list references the room itself.
+    SelfReference { room_id: String, relation: RelationKind },
+    /// A relationship list references a room that is not in the requirements.
+    UnknownRoom {
+        room_id: String,
+        referenced_id: String,
+        relation: RelationKind,
+    },
+    /// A pair of rooms is required to be both adjacent and not adjacent.
+    ConflictingAdjacency { room_id: String, other_room_id: String },
+    /// A room names the same room in more than one adjacency relation
+    /// (`adjacent_to`, `not_adjacent_to` or `relations`), which would score the pair twice.
+    DuplicateRelation { room_id: String, other_room_id: String },
+    /// A weighted relation or position constraint has a negative or non-numeric weight.
+    InvalidRelationWeight { room_id: String, other_room_id: String, weight: f64 },
+    /// A distance constraint has no bounds, a negative bound, min above max or a negative weight.
+    InvalidDistanceConstraint { room_id: String, other_room_id: String },
+    /// A room count range is empty or inverted.
+    InvalidCount { room_id: String, min_count: Option<usize>, max_count: Option<usize> },
+    /// Corridors are requested with a non-positive width, one wider than the
+    /// boundary, or without an entrance.
+    InvalidCorridorSettings { corridor_width: f64, entrance_count: usize },
+    /// An entrance is needed but none is defined: by the room that must contain
+    /// one, or by the reachability check when `room_id` is `None`.
+    NoEntrance { room_id: Option<String> },
+    /// A wall thickness is negative or not a number.
+    InvalidWallThickness { interior: f64, exterior: f64 },
+    /// An entrance does not lie on the outline of the boundary.
+    EntranceOffBoundary { x: f64, y: f64 },
+    /// A room group in the solver config references a room that is not in the requirements.
+    UnknownGroupMember { referenced_id: String },
+    /// A room group has fewer than two distinct rooms or a negative or non-numeric weight,
+    /// or a mirror group has unpaired rooms or reuses a room from another pair.
+    InvalidGroup { room_ids: Vec<String> },
+    /// Mirror groups are set for a strategy that lays out every room itself.
+    MirrorGroupsUnsupported { strategy: SolverStrategy },
+    /// The annealing temperature is negative or the cooling rate is outside `(0, 1]`.
+    InvalidAnnealingSettings { initial_temperature: f64, cooling_rate: f64 },
+    /// A door width is not positive, the minimum exceeds the preferred width,
+    /// or the corner clearance is negative.
+    InvalidDoorSettings { width: f64, min_width: f64, corner_clearance: f64 },
+    /// The window height is not positive or the corner clearance is negative.
+    InvalidWindowSettings { height: f64, corner_clearance: f64 },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::DuplicateId { room_id } => {
+                write!(f, "Room id {} is used more than once", room_id)
+            }
+            ValidationError::NonPositiveArea { room_id, min_area } => {
+                write!(f, "Room {} has a non-positive min_area ({})", room_id, min_area)
+            }
+            ValidationError::InvalidBound { room_id, bound, value } => {
+                write!(f, "Room {} has an invalid {} ({})", room_id, bound, value)
+            }
+            ValidationError::ConflictingBounds { room_id, lower, upper } => {
+                write!(f, "Room {} has {} greater than {}", room_id, lower, upper)
+            }
+            ValidationError::SelfReference { room_id, relation } => {
+                write!(f, "Room {} lists itself in {}", room_id, relation)
+            }
+            ValidationError::UnknownRoom { room_id, referenced_id, relation } => write!(
+                f,
+                "Room {} references unknown room {} in {}",
+                room_id, referenced_id, relation
+            ),
+            ValidationError::ConflictingAdjacency { room_id, other_room_id } => write!(
+                f,
+                "Room {} is required to be both adjacent and not adjacent to {}",
+                room_id, other_room_id
+            ),
+            ValidationError::DuplicateRelation { room_id, other_room_id } => write!(
+                f,
+                "Room {} has more than one adjacency relation to {}",
+                room_id, other_room_id
+            ),
+            ValidationError::InvalidRelationWeight { room_id, other_room_id, weight } => write!(
+                f,
+                "Room {} has an invalid weight ({}) on its relation to {}",
+                room_id, weight, other_room_id
+            ),
+            ValidationError::InvalidDistanceConstraint { room_id, other_room_id } => write!(
+                f,
+                "Room {} has an invalid distance constraint to {}",
+                room_id, other_room_id
+            ),
+            ValidationError::InvalidCount { room_id, min_count, max_count } => write!(
+                f,
+                "Room {} has an invalid count range ({:?} to {:?})",
+                room_id, min_count, max_count
+            ),
+            ValidationError::InvalidCorridorSettings { corridor_width, entrance_count } => write!(
+                f,
+                "Corridors need a positive width that fits the boundary and at least one entrance \
+                 (width {}, {} entrances)",
+                corridor_width, entrance_count
+            ),
+            ValidationError::NoEntrance { room_id: Some(room_id) } => {
+                write!(f, "Room {} must contain an entrance but none is defined", room_id)
+            }
+            ValidationError::NoEntrance { room_id: None } => {
+                write!(f, "The reachability check needs at least one entrance")
+            }
+            ValidationError::InvalidWallThickness { interior, exterior } => write!(
+                f,
+                "Wall thicknesses must be non-negative (interior {}, exterior {})",
+                interior, exterior
+            ),
+            ValidationError::EntranceOffBoundary { x, y } => {
+                write!(f, "Entrance at ({}, {}) is not on the boundary", x, y)
+            }
+            ValidationError::UnknownGroupMember { referenced_id } => {
+                write!(f, "A room group references unknown room {}", referenced_id)
+            }
+            ValidationError::InvalidGroup { room_ids } => {
+                write!(f, "Room group {:?} is malformed", room_ids)
+            }
+            ValidationError::MirrorGroupsUnsupported { strategy } => {
+                write!(f, "Mirror groups need the backtracking strategy, not {:?}", strategy)
+            }
+            ValidationError::InvalidAnnealingSettings { initial_temperature, cooling_rate } => write!(
+                f,
+                "Annealing needs a non-negative temperature and a cooling rate in (0, 1] (temperature {}, rate {})",
+                initial_temperature, cooling_rate
+            ),
+            ValidationError::InvalidDoorSettings { width, min_width, corner_clearance } => write!(
+                f,
+                "Doors need positive widths with min_width at most width and a non-negative corner clearance \
+                 (width {}, min_width {}, clearance {})",
+                width, min_width, corner_clearance
+            ),
+            ValidationError::InvalidWindowSettings { height, corner_clearance } => write!(
+                f,
+                "Windows need a positive height and a non-negative corner clearance (height {}, clearance {})",
+                height, corner_clearance
+            ),
+        }
+    }
+}
+
+/// Check room requirements for structural problems before solving.
+///
+/// Collects every problem rather than stopping at the first one, so the
+/// result can drive form validation in the UI.
+pub fn validate_requirements(room_requirements: &[RoomRequirement]) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+
+    let mut seen_ids = HashSet::new();
+    let mut reported_duplicates = HashSet::new();
+    for room_req in room_requirements {
+        if !seen_ids.insert(room_req.id.as_str()) && reported_duplicates.insert(room_req.id.as_str()) {
+            errors.push(ValidationError::DuplicateId { room_id: room_req.id.clone() });
+        }
+    }
+
+    for room_req in room_requirements {
+        if room_req.min_area.is_nan() || room_req.min_area <= 0.0 {
+            errors.push(ValidationError::NonPositiveArea {
+                room_id: room_req.id.clone(),
+                min_area: room_req.min_area,
+            });
+        }
+
+        errors.extend(check_size_bounds(room_req));
+
+        let inverted = matches!(
+            (room_req.min_count, room_req.max_count),
+            (Some(min_count), Some(max_count)) if min_count > max_count
+        );
+        if inverted || room_req.instance_count() == 0 {
+            errors.push(ValidationError::InvalidCount {
+                room_id: room_req.id.clone(),
+                min_count: room_req.min_count,
+                max_count: room_req.max_count,
+            });
+        }
+
+        let relation_ids: Vec<&String> = room_req.relations.iter().map(|r| &r.room_id).collect();
+        let distance_ids: Vec<&String> = room_req.distance_constraints.iter().map(|c| &c.room_id).collect();
+        let position_ids: Vec<&String> = room_req.position_constraints.iter().map(|c| &c.room_id).collect();
+        let relations = [
+            (RelationKind::AdjacentTo, room_req.adjacent_to.iter().collect::<Vec<_>>()),
+            (RelationKind::NotAdjacentTo, room_req.not_adjacent_to.iter().collect()),
+            (RelationKind::Relations, relation_ids),
+            (RelationKind::DistanceConstraints, distance_ids),
+            (RelationKind::PositionConstraints, position_ids),
+        ];
+        for (relation, referenced_ids) in relations {
+            for referenced_id in referenced_ids {
+                if *referenced_id == room_req.id {
+                    errors.push(ValidationError::SelfReference { room_id: room_req.id.clone(), relation });
+                } else if !seen_ids.contains(referenced_id.as_str()) {
+                    errors.push(ValidationError::UnknownRoom {
+                        room_id: room_req.id.clone(),
+                        referenced_id: referenced_id.clone(),
+                        relation,
+                    });
+                }
+            }
+        }
+
+        let weights = room_req
+            .relations
+            .iter()
+            .map(|r| (&r.room_id, r.weight))
+            .chain(room_req.position_constraints.iter().map(|c| (&c.room_id, c.weight)));
+        for (other_room_id, weight) in weights {
+            if weight.is_nan() || weight < 0.0 {
+                errors.push(ValidationError::InvalidRelationWeight {
+                    room_id: room_req.id.clone(),
+                    other_room_id: other_room_id.clone(),
+                    weight,
+                });
+            }
+        }
+
+        for constraint in room_req.distance_constraints.iter() {
+            let invalid_bound = |bound: Option<f64>| bound.is_some_and(|v| v.is_nan() || v < 0.0);
+            let no_bounds = constraint.min_distance.is_none() && constraint.max_distance.is_none();
+            let inverted = matches!(
+                (constraint.min_distance, constraint.max_distance),
+                (Some(min_distance), Some(max_distance)) if min_distance > max_distance
+            );
+            let invalid_weight = constraint.weight.is_nan() || constraint.weight < 0.0;
+
+            if no_bounds || inverted || invalid_weight
+                || invalid_bound(constraint.min_distance)
+                || invalid_bound(constraint.max_distance)
+            {
+                errors.push(ValidationError::InvalidDistanceConstraint {
+                    room_id: room_req.id.clone(),
+                    other_room_id: constraint.room_id.clone(),
+                });
+            }
+        }
+
+        let mut related_ids: Vec<String> = Vec::new();
+        let mut duplicate_ids: Vec<String> = Vec::new();
+        for relation in room_req.adjacency_relations() {
+            if related_ids.contains(&relation.room_id) {
+                if !duplicate_ids.contains(&relation.room_id) {
+                    duplicate_ids.push(relation.room_id);
+                }
+            } else {
+                related_ids.push(relation.room_id);
+            }
+        }
+
+        let forbidden = room_req.related_rooms(AdjacencyStrength::Forbidden);
+        let required = room_req.related_rooms(AdjacencyStrength::Required);
+        // Required and forbidden together are reported as a conflict instead
+        for other_room_id in duplicate_ids {
+            if !(required.contains(&other_room_id) && forbidden.contains(&other_room_id)) {
+                errors.push(ValidationError::DuplicateRelation { room_id: room_req.id.clone(), other_room_id });
+            }
+        }
+
+        for adjacent in required.iter() {
+            // Conflict within this room's own relations, or with the other room forbidding this one
+            let forbidden_here = forbidden.contains(adjacent);
+            let forbidden_there = room_requirements.iter().any(|other| {
+                other.id == *adjacent && other.related_rooms(AdjacencyStrength::Forbidden).contains(&room_req.id)
+            });
+
+            if *adjacent != room_req.id && (forbidden_here || forbidden_there) {
+                errors.push(ValidationError::ConflictingAdjacency {
+                    room_id: room_req.id.clone(),
+                    other_room_id: adjacent.clone(),
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Check the groups, corridors, walls, doors, windows and entrances in a solver config against the requirements.
+///
+/// Groups name room instances, so pass the requirements after
+/// `expand_instances`. Corridors are checked against the boundary size.
+pub fn validate_config(
+    room_requirements: &[RoomRequirement],
+    config: &SolverConfig,
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    let known_ids: HashSet<&str> = room_requirements.iter().map(|r| r.id.as_str()).collect();
+
+    for group in config.alignment_groups.iter() {
+        for room_id in group.room_ids.iter() {
+            if !known_ids.contains(room_id.as_str()) {
+                errors.push(ValidationError::UnknownGroupMember { referenced_id: room_id.clone() });
+            }
+        }
+
+        let distinct: HashSet<&String> = group.room_ids.iter().collect();
+        if distinct.len() < 2 || group.weight.is_nan() || group.weight < 0.0 {
+            errors.push(ValidationError::InvalidGroup { room_ids: group.room_ids.clone() });
+        }
+    }
+
+    // Each room may take part in at most one mirror pair, on one side of it
+    let mut mirrored_ids = HashSet::new();
+    for group in config.mirror_groups.iter() {
+        let all_ids: Vec<String> = group.room_ids.iter().chain(group.mirrored_ids.iter()).cloned().collect();
+        for room_id in all_ids.iter() {
+            if !known_ids.contains(room_id.as_str()) {
+                errors.push(ValidationError::UnknownGroupMember { referenced_id: room_id.clone() });
+            }
+        }
+
+        let reused = all_ids.iter().any(|room_id| !mirrored_ids.insert(room_id.clone()));
+        if group.room_ids.is_empty() || group.room_ids.len() != group.mirrored_ids.len() || reused {
+            errors.push(ValidationError::InvalidGroup { room_ids: all_ids });
+        }
+    }
+    if !config.mirror_groups.is_empty() && config.strategy != SolverStrategy::Backtracking {
+        errors.push(ValidationError::MirrorGroupsUnsupported { strategy: config.strategy });
+    }
+
+    if let Some(corridor_width) = config.corridor_width
+        && (corridor_width.is_nan()
+            || corridor_width <= 0.0
+            || corridor_width > boundary_width.min(boundary_height)
+            || config.entrances.is_empty())
+    {
+        errors.push(ValidationError::InvalidCorridorSettings {
+            corridor_width,
+            entrance_count: config.entrances.len(),
+        });
+    }
+
+    if let Some(walls) = config.walls {
+        let invalid = |thickness: f64| thickness.is_nan() || thickness < 0.0;
+        if invalid(walls.interior) || invalid(walls.exterior) {
+            errors.push(ValidationError::InvalidWallThickness { interior: walls.interior, exterior: walls.exterior });
+        }
+    }
+
+    let doors = &config.doors;
+    let is_positive = |value: f64| value.is_finite() && value > 0.0;
+    let is_clearance = |value: f64| value.is_finite() && value >= 0.0;
+    if !(is_positive(doors.width)
+        && is_positive(doors.min_width)
+        && doors.min_width <= doors.width
+        && is_clearance(doors.corner_clearance))
+    {
+        errors.push(ValidationError::InvalidDoorSettings {
+            width: doors.width,
+            min_width: doors.min_width,
+            corner_clearance: doors.corner_clearance,
+        });
+    }
+
+    let windows = &config.windows;
+    if !(is_positive(windows.height) && is_clearance(windows.corner_clearance)) {
+        errors.push(ValidationError::InvalidWindowSettings {
+            height: windows.height,
+            corner_clearance: windows.corner_clearance,
+        });
+    }
+
+    let annealing = &config.annealing;
+    if !(annealing.initial_temperature >= 0.0 && annealing.cooling_rate > 0.0 && annealing.cooling_rate <= 1.0) {
+        errors.push(ValidationError::InvalidAnnealingSettings {
+            initial_temperature: annealing.initial_temperature,
+            cooling_rate: annealing.cooling_rate,
+        });
+    }
+
+    if config.entrances.is_empty() {
+        for room_req in room_requirements.iter().filter(|r| r.contains_entrance) {
+            errors.push(ValidationError::NoEntrance { room_id: Some(room_req.id.clone()) });
+        }
+        if config.require_reachability {
+            errors.push(ValidationError::NoEntrance { room_id: None });
+        }
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Check that every entrance lies on the outline of the boundary.
+pub fn validate_entrances(
+    entrances: &[Entrance],
+    boundary_width: f64,
+    boundary_height: f64,
+) -> Result<(), Vec<ValidationError>> {
+    let errors: Vec<ValidationError> = entrances
+        .iter()
+        .filter(|entrance| !entrance.is_on_perimeter(boundary_width, boundary_height))
+        .map(|entrance| ValidationError::EntranceOffBoundary { x: entrance.x, y: entrance.y })
+        .collect();
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+fn check_size_bounds(room_req: &RoomRequirement) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    // Areas and lengths must be positive; aspect ratios are long side over short side
+    let bounds = [
+        (SizeBound::MaxArea, room_req.max_area, false),
+        (SizeBound::MinWidth, room_req.min_width, false),
+        (SizeBound::MinDepth, room_req.min_depth, false),
+        (SizeBound::MinAspect, room_req.min_aspect, true),
+        (SizeBound::MaxAspect, room_req.max_aspect, true),
+        (SizeBound::MinFacadeLength, room_req.min_facade_length, false),
+        (SizeBound::GlazingRatio, room_req.glazing_ratio, false),
+    ];
+    for (bound, value, is_aspect) in bounds {
+        let Some(value) = value else { continue };
+        let out_of_range = if is_aspect { value < 1.0 } else { value <= 0.0 };
+        if value.is_nan() || out_of_range {
+            errors.push(ValidationError::InvalidBound { room_id: room_req.id.clone(), bound, value });
+        }
+    }
+
+    if room_req.max_area.is_some_and(|max_area| max_area < room_req.min_area) {
+        errors.push(ValidationError::ConflictingBounds {
+            room_id: room_req.id.clone(),
+            lower: SizeBound::MinArea,
+            upper: SizeBound::MaxArea,
+        });
+    }
+
+    if let (Some(min_aspect), Some(max_aspect)) = (room_req.min_aspect, room_req.max_aspect)
+        && min_aspect > max_aspect
+    {
+        errors.push(ValidationError::ConflictingBounds {
+            room_id: room_req.id.clone(),
+            lower: SizeBound::MinAspect,
+            upper: SizeBound::MaxAspect,
+        });
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AdjacencyRelation, DistanceConstraint, DistanceMetric};
+
+    fn room(id: &str, min_area: f64, adjacent_to: &[&str], not_adjacent_to: &[&str]) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area,
+            adjacent_to: adjacent_to.iter().map(|s| s.to_string()).collect(),
+            not_adjacent_to: not_adjacent_to.iter().map(|s| s.to_string()).collect(),
+            has_exterior_wall: false,
+            ..Default::default()
+        }
+    }
+
+    // Test 1: test_valid_requirements_pass
+    #[test]
+    fn test_valid_requirements_pass() {
+        let rooms = vec![
+            room("living", 20.0, &["kitchen"], &["bathroom"]),
+            room("kitchen", 12.0, &[], &[]),
+            room("bathroom", 6.0, &[], &[]),
+        ];
+        assert_eq!(validate_requirements(&rooms), Ok(()));
+    }
+
+    // Test 2: test_duplicate_id_reported_once
+    #[test]
+    fn test_duplicate_id_reported_once() {
+        let rooms = vec![
+            room("bedroom", 9.0, &[], &[]),
+            room("bedroom", 9.0, &[], &[]),
+            room("bedroom", 9.0, &[], &[]),
+        ];
+        let errors = validate_requirements(&rooms).unwrap_err();
+        assert_eq!(errors, vec![ValidationError::DuplicateId { room_id: "bedroom".to_string() }]);
+    }
+
+    // Test 3: test_unknown_room_reference
+    #[test]
+    fn test_unknown_room_reference() {
+        let rooms = vec![room("living", 20.0, &["garage"], &[])];
+        let errors = validate_requirements(&rooms).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ValidationError::UnknownRoom {
+                room_id: "living".to_string(),
+                referenced_id: "garage".to_string(),
+                relation: RelationKind::AdjacentTo,
+            }]
+        );
+    }
+
+    // Test 4: test_conflicting_adjacency_within_and_across_rooms
+    #[test]
+    fn test_conflicting_adjacency_within_and_across_rooms() {
+        let rooms = vec![
+            room("living", 20.0, &["kitchen"], &["kitchen"]),
+            room("kitchen", 12.0, &["bathroom"], &[]),
+            room("bathroom", 6.0, &[], &["kitchen"]),
+        ];
+        let errors = validate_requirements(&rooms).unwrap_err();
+        assert!(errors.contains(&ValidationError::ConflictingAdjacency {
+            room_id: "living".to_string(),
+            other_room_id: "kitchen".to_string(),
+        }));
+        assert!(errors.contains(&ValidationError::ConflictingAdjacency {
+            room_id: "kitchen".to_string(),
+            other_room_id: "bathroom".to_string(),
+        }));
+    }
+
+    // Test 5: test_non_positive_area_and_self_reference_all_reported
+    #[test]
+    fn test_non_positive_area_and_self_reference_all_reported() {
+        let rooms = vec![room("hall", 0.0, &["hall"], &[]), room("store", f64::NAN, &[], &[])];
+        let errors = validate_requirements(&rooms).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|e| matches!(e, ValidationError::NonPositiveArea { room_id, .. } if room_id == "hall")));
+        assert!(errors.iter().any(|e| matches!(e, ValidationError::NonPositiveArea { room_id, .. } if room_id == "store")));
+        assert!(errors.iter().any(|e| matches!(e, ValidationError::SelfReference { relation: RelationKind::AdjacentTo, .. })));
+    }
+
+    // Test 6: test_invalid_and_conflicting_size_bounds
+    #[test]
+    fn test_invalid_and_conflicting_size_bounds() {
+        let rooms = vec![RoomRequirement {
+            id: "bedroom".to_string(),
+            min_area: 12.0,
+            max_area: Some(10.0),
+            min_width: Some(0.0),
+            min_aspect: Some(2.0),
+            max_aspect: Some(1.5),
+            ..Default::default()
+        }];
+        let errors = validate_requirements(&rooms).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&ValidationError::InvalidBound {
+            room_id: "bedroom".to_string(),
+            bound: SizeBound::MinWidth,
+            value: 0.0,
+        }));
+        assert!(errors.contains(&ValidationError::ConflictingBounds {
+            room_id: "bedroom".to_string(),
+            lower: SizeBound::MinArea,
+            upper: SizeBound::MaxArea,
+        }));
+        assert!(errors.contains(&ValidationError::ConflictingBounds {
+            room_id: "bedroom".to_string(),
+            lower: SizeBound::MinAspect,
+            upper: SizeBound::MaxAspect,
+        }));
+    }
+
+    // Test 7: test_relations_checked_for_references_weights_and_conflicts
+    #[test]
+    fn test_relations_checked_for_references_weights_and_conflicts() {
+        let relation = |room_id: &str, strength: AdjacencyStrength, weight: f64| AdjacencyRelation {
+            room_id: room_id.to_string(),
+            strength,
+            weight,
+        };
+        let mut living = room("living", 20.0, &["kitchen"], &[]);
+        living.relations = vec![
+            relation("kitchen", AdjacencyStrength::Forbidden, 0.0),
+            relation("garage", AdjacencyStrength::Preferred, 2.0),
+            relation("bathroom", AdjacencyStrength::Avoid, -1.0),
+        ];
+        let rooms = vec![living, room("kitchen", 12.0, &[], &[]), room("bathroom", 6.0, &[], &[])];
+
+        let errors = validate_requirements(&rooms).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&ValidationError::UnknownRoom {
+            room_id: "living".to_string(),
+            referenced_id: "garage".to_string(),
+            relation: RelationKind::Relations,
+        }));
+        assert!(errors.contains(&ValidationError::InvalidRelationWeight {
+            room_id: "living".to_string(),
+            other_room_id: "bathroom".to_string(),
+            weight: -1.0,
+        }));
+        assert!(errors.contains(&ValidationError::ConflictingAdjacency {
+            room_id: "living".to_string(),
+            other_room_id: "kitchen".to_string(),
+        }));
+    }
+
+    // Test 8: test_distance_constraints_checked
+    #[test]
+    fn test_distance_constraints_checked() {
+        let constraint = |room_id: &str, min_distance, max_distance| DistanceConstraint {
+            room_id: room_id.to_string(),
+            min_distance,
+            max_distance,
+            metric: DistanceMetric::CenterToCenter,
+            hard: true,
+            weight: 1.0,
+        };
+        let mut master = room("master", 12.0, &[], &[]);
+        master.distance_constraints = vec![
+            constraint("living", Some(6.0), Some(4.0)),
+            constraint("kitchen", None, None),
+            constraint("garage", Some(2.0), None),
+        ];
+        let rooms = vec![master, room("living", 20.0, &[], &[]), room("kitchen", 12.0, &[], &[])];
+
+        let errors = validate_requirements(&rooms).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&ValidationError::InvalidDistanceConstraint {
+            room_id: "master".to_string(),
+            other_room_id: "living".to_string(),
+        }));
+        assert!(errors.contains(&ValidationError::InvalidDistanceConstraint {
+            room_id: "master".to_string(),
+            other_room_id: "kitchen".to_string(),
+        }));
+        assert!(errors.contains(&ValidationError::UnknownRoom {
+            room_id: "master".to_string(),
+            referenced_id: "garage".to_string(),
+            relation: RelationKind::DistanceConstraints,
+        }));
+    }
+
+    // Test 9: test_config_groups_checked
+    #[test]
+    fn test_config_groups_checked() {
+        use crate::types::{Alignment, AlignmentGroup};
+
+        let rooms = vec![room("bath", 4.0, &[], &[]), room("ensuite", 3.0, &[], &[])];
+        let group = |room_ids: &[&str]| AlignmentGroup {
+            room_ids: room_ids.iter().map(|s| s.to_string()).collect(),
+            alignment: Alignment::Top,
+            hard: true,
+            weight: 1.0,
+        };
+        let config = SolverConfig::default()
+            .with_alignment_group(group(&["bath", "ensuite"]))
+            .with_alignment_group(group(&["bath", "bath"]))
+            .with_alignment_group(group(&["bath", "laundry"]));
+
+        let errors = validate_config(&rooms, &config, 10.0, 8.0).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::InvalidGroup { room_ids: vec!["bath".to_string(), "bath".to_string()] },
+                ValidationError::UnknownGroupMember { referenced_id: "laundry".to_string() },
+            ]
+        );
+    }
+
+    // Test 10: test_mirror_groups_checked
+    #[test]
+    fn test_mirror_groups_checked() {
+        use crate::types::{MirrorAxis, MirrorGroup};
+
+        let rooms = vec![room("unit_a", 40.0, &[], &[]), room("unit_b", 40.0, &[], &[]), room("stair", 8.0, &[], &[])];
+        let group = |room_ids: &[&str], mirrored_ids: &[&str]| MirrorGroup {
+            room_ids: room_ids.iter().map(|s| s.to_string()).collect(),
+            mirrored_ids: mirrored_ids.iter().map(|s| s.to_string()).collect(),
+            axis: MirrorAxis::Vertical,
+        };
+
+        let valid = SolverConfig::default().with_mirror_group(group(&["unit_a"], &["unit_b"]));
+        assert_eq!(validate_config(&rooms, &valid, 10.0, 8.0), Ok(()));
+
+        let dual = SolverConfig { strategy: SolverStrategy::RectangularDual, ..Default::default() }
+            .with_mirror_group(group(&["unit_a"], &["unit_b"]));
+        assert_eq!(
+            validate_config(&rooms, &dual, 10.0, 8.0),
+            Err(vec![ValidationError::MirrorGroupsUnsupported { strategy: SolverStrategy::RectangularDual }])
+        );
+
+        let mut annealing = SolverConfig { strategy: SolverStrategy::SlicingTree, ..Default::default() };
+        annealing.annealing.cooling_rate = 1.5;
+        assert_eq!(
+            validate_config(&rooms, &annealing, 10.0, 8.0),
+            Err(vec![ValidationError::InvalidAnnealingSettings { initial_temperature: 50.0, cooling_rate: 1.5 }])
+        );
+
+        let unpaired = SolverConfig::default().with_mirror_group(group(&["unit_a", "stair"], &["unit_b"]));
+        assert!(matches!(
+            validate_config(&rooms, &unpaired, 10.0, 8.0).unwrap_err().as_slice(),
+            [ValidationError::InvalidGroup { .. }]
+        ));
+
+        // stair is reused by a second pair
+        let reused = SolverConfig::default()
+            .with_mirror_group(group(&["unit_a"], &["stair"]))
+            .with_mirror_group(group(&["stair"], &["unit_b"]));
+        assert!(matches!(
+            validate_config(&rooms, &reused, 10.0, 8.0).unwrap_err().as_slice(),
+            [ValidationError::InvalidGroup { .. }]
+        ));
+    }
+
+    // Test 11: test_invalid_count_ranges
+    #[test]
+    fn test_invalid_count_ranges() {
+        let mut inverted = room("closet", 1.0, &[], &[]);
+        inverted.min_count = Some(3);
+        inverted.max_count = Some(2);
+        let mut empty = room("storage", 1.0, &[], &[]);
+        empty.max_count = Some(0);
+        let mut range = room("bedroom", 9.0, &[], &[]);
+        range.min_count = Some(2);
+        range.max_count = Some(4);
+
+        let errors = validate_requirements(&[inverted, empty, range]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::InvalidCount { room_id: "closet".to_string(), min_count: Some(3), max_count: Some(2) },
+                ValidationError::InvalidCount { room_id: "storage".to_string(), min_count: None, max_count: Some(0) },
+            ]
+        );
+    }
+
+    // Test 12: test_entrances_checked
+    #[test]
+    fn test_entrances_checked() {
+        let on_edge = Entrance { x: 5.0, y: 8.0 };
+        let inside = Entrance { x: 5.0, y: 4.0 };
+        assert_eq!(validate_entrances(&[on_edge], 10.0, 8.0), Ok(()));
+        assert_eq!(
+            validate_entrances(&[on_edge, inside], 10.0, 8.0),
+            Err(vec![ValidationError::EntranceOffBoundary { x: 5.0, y: 4.0 }])
+        );
+
+        let mut foyer = room("foyer", 4.0, &[], &[]);
+        foyer.contains_entrance = true;
+        let config = SolverConfig { require_reachability: true, ..Default::default() };
+        assert_eq!(
+            validate_config(&[foyer], &config, 10.0, 8.0),
+            Err(vec![
+                ValidationError::NoEntrance { room_id: Some("foyer".to_string()) },
+                ValidationError::NoEntrance { room_id: None },
+            ])
+        );
+    }
+
+    // Test 13: test_duplicate_relations_rejected
+    #[test]
+    fn test_duplicate_relations_rejected() {
+        use crate::types::AdjacencyRelation;
+
+        let mut living = room("living", 20.0, &["kitchen"], &[]);
+        living.relations.push(AdjacencyRelation {
+            room_id: "kitchen".to_string(),
+            strength: AdjacencyStrength::Preferred,
+            weight: 2.0,
+        });
+        let rooms = vec![living, room("kitchen", 12.0, &["living"], &[])];
+
+        assert_eq!(
+            validate_requirements(&rooms),
+            Err(vec![ValidationError::DuplicateRelation {
+                room_id: "living".to_string(),
+                other_room_id: "kitchen".to_string(),
+            }])
+        );
+    }
+
+    // Test 14: test_corridor_settings_checked
+    #[test]
+    fn test_corridor_settings_checked() {
+        let rooms = vec![room("living", 20.0, &[], &[])];
+        let corridors = |corridor_width: f64| SolverConfig {
+            corridor_width: Some(corridor_width),
+            entrances: vec![Entrance { x: 5.0, y: 8.0 }],
+            ..Default::default()
+        };
+
+        assert_eq!(validate_config(&rooms, &corridors(1.2), 10.0, 8.0), Ok(()));
+        assert_eq!(validate_config(&rooms, &corridors(8.0), 10.0, 8.0), Ok(()));
+        // Wider than the short side of the boundary
+        assert_eq!(
+            validate_config(&rooms, &corridors(9.0), 10.0, 8.0),
+            Err(vec![ValidationError::InvalidCorridorSettings { corridor_width: 9.0, entrance_count: 1 }])
+        );
+        assert!(validate_config(&rooms, &corridors(0.0), 10.0, 8.0).is_err());
+    }
+
+    // Test 15: test_door_settings_checked
+    #[test]
+    fn test_door_settings_checked() {
+        use crate::doors::DoorSettings;
+
+        let rooms = vec![room("living", 20.0, &[], &[])];
+        let doors = |width: f64, min_width: f64| SolverConfig {
+            doors: DoorSettings { width, min_width, ..Default::default() },
+            ..Default::default()
+        };
+
+        assert_eq!(validate_config(&rooms, &doors(0.9, 0.9), 10.0, 8.0), Ok(()));
+        assert_eq!(
+            validate_config(&rooms, &doors(0.8, 0.9), 10.0, 8.0),
+            Err(vec![ValidationError::InvalidDoorSettings { width: 0.8, min_width: 0.9, corner_clearance: 0.1 }])
+        );
+        assert!(validate_config(&rooms, &doors(0.9, 0.0), 10.0, 8.0).is_err());
+        assert!(validate_config(&rooms, &doors(-0.9, -1.0), 10.0, 8.0).is_err());
+        assert!(validate_config(&rooms, &doors(f64::INFINITY, 0.7), 10.0, 8.0).is_err());
+    }
+
+    // Test 16: test_window_settings_checked
+    #[test]
+    fn test_window_settings_checked() {
+        use crate::windows::WindowSettings;
+
+        let rooms = vec![room("living", 20.0, &[], &[])];
+        let windows = |height: f64, corner_clearance: f64| SolverConfig {
+            windows: WindowSettings { height, corner_clearance },
+            ..Default::default()
+        };
+
+        assert_eq!(validate_config(&rooms, &windows(1.2, 0.0), 10.0, 8.0), Ok(()));
+        // A zero height would divide the glazing target by zero
+        assert_eq!(
+            validate_config(&rooms, &windows(0.0, 0.3), 10.0, 8.0),
+            Err(vec![ValidationError::InvalidWindowSettings { height: 0.0, corner_clearance: 0.3 }])
+        );
+        assert!(validate_config(&rooms, &windows(-1.2, 0.3), 10.0, 8.0).is_err());
+        assert!(validate_config(&rooms, &windows(f64::NAN, 0.3), 10.0, 8.0).is_err());
+        assert!(validate_config(&rooms, &windows(1.2, -0.3), 10.0, 8.0).is_err());
+    }
+}
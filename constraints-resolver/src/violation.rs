@@ -0,0 +1,249 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Alignment, BoundarySide, DistanceMetric, Orientation, RelativePosition, Zone};
+
+/// A room dimension named by a size bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dimension {
+    /// Extent along x.
+    Width,
+    /// Extent along y.
+    Depth,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::Width => write!(f, "width"),
+            Dimension::Depth => write!(f, "depth"),
+        }
+    }
+}
+
+/// A hard constraint broken by a candidate placement.
+///
+/// Each variant names the constraint kind and carries the room ids involved
+/// plus any geometric detail needed to explain it. Serialised with a `kind`
+/// tag so JavaScript callers can switch on it directly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Violation {
+    /// The room extends past the layout boundary.
+    OutsideBoundary { room_id: String },
+    /// The room shares area with an already placed room.
+    Overlap {
+        room_id: String,
+        other_room_id: String,
+        overlap_area: f64,
+    },
+    /// The room is smaller than its minimum area.
+    AreaTooSmall { room_id: String, area: f64, min_area: f64 },
+    /// The room is larger than its maximum area.
+    AreaTooLarge { room_id: String, area: f64, max_area: f64 },
+    /// A side of the room is shorter than its minimum.
+    DimensionTooSmall {
+        room_id: String,
+        dimension: Dimension,
+        actual: f64,
+        minimum: f64,
+    },
+    /// The room is more elongated or more square than allowed.
+    AspectRatioOutOfRange {
+        room_id: String,
+        aspect_ratio: f64,
+        min_aspect: Option<f64>,
+        max_aspect: Option<f64>,
+    },
+    /// The room requires an exterior wall but touches none.
+    MissingExteriorWall { room_id: String },
+    /// The room must touch one of the listed boundary sides but touches none of them.
+    NotOnRequiredSide { room_id: String, sides: Vec<BoundarySide> },
+    /// The room has too little exterior wall facing the required directions.
+    InsufficientFacade {
+        room_id: String,
+        orientations: Vec<Orientation>,
+        facade_length: f64,
+        min_facade_length: f64,
+    },
+    /// The room is not adjacent to a room it must be adjacent to.
+    MissingAdjacency {
+        room_id: String,
+        required_room_id: String,
+    },
+    /// The room shares a wall with a room it must not be adjacent to.
+    ForbiddenAdjacency {
+        room_id: String,
+        forbidden_room_id: String,
+        shared_edge_length: f64,
+    },
+    /// The room is closer to or further from another room than a hard distance constraint allows.
+    DistanceOutOfRange {
+        room_id: String,
+        other_room_id: String,
+        metric: DistanceMetric,
+        distance: f64,
+        min_distance: Option<f64>,
+        max_distance: Option<f64>,
+    },
+    /// The room is not on the required side of another room.
+    RelativePositionViolated {
+        room_id: String,
+        other_room_id: String,
+        position: RelativePosition,
+    },
+    /// The room does not line up with another member of a hard alignment group.
+    Misaligned {
+        room_id: String,
+        other_room_id: String,
+        alignment: Alignment,
+    },
+    /// The room does not share a wall with the cluster of its zone already placed.
+    ZoneNotContiguous { room_id: String, zone: Zone },
+    /// The room must contain an entrance but none lies on its outline.
+    MissingEntrance { room_id: String },
+}
+
+impl Violation {
+    /// The id of the room whose placement caused the violation.
+    pub fn room_id(&self) -> &str {
+        match self {
+            Violation::OutsideBoundary { room_id }
+            | Violation::Overlap { room_id, .. }
+            | Violation::AreaTooSmall { room_id, .. }
+            | Violation::AreaTooLarge { room_id, .. }
+            | Violation::DimensionTooSmall { room_id, .. }
+            | Violation::AspectRatioOutOfRange { room_id, .. }
+            | Violation::MissingExteriorWall { room_id }
+            | Violation::NotOnRequiredSide { room_id, .. }
+            | Violation::InsufficientFacade { room_id, .. }
+            | Violation::MissingAdjacency { room_id, .. }
+            | Violation::ForbiddenAdjacency { room_id, .. }
+            | Violation::DistanceOutOfRange { room_id, .. }
+            | Violation::RelativePositionViolated { room_id, .. }
+            | Violation::Misaligned { room_id, .. }
+            | Violation::ZoneNotContiguous { room_id, .. }
+            | Violation::MissingEntrance { room_id } => room_id,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::OutsideBoundary { room_id } => {
+                write!(f, "Room {} is outside the boundary", room_id)
+            }
+            Violation::Overlap { room_id, other_room_id, overlap_area } => write!(
+                f,
+                "Room {} overlaps with already placed room {} ({:.2} m²)",
+                room_id, other_room_id, overlap_area
+            ),
+            Violation::AreaTooSmall { room_id, area, min_area } => write!(
+                f,
+                "Room {} has {:.2} m², below its minimum of {:.2} m²",
+                room_id, area, min_area
+            ),
+            Violation::AreaTooLarge { room_id, area, max_area } => write!(
+                f,
+                "Room {} has {:.2} m², above its maximum of {:.2} m²",
+                room_id, area, max_area
+            ),
+            Violation::DimensionTooSmall { room_id, dimension, actual, minimum } => write!(
+                f,
+                "Room {} {} is {:.2} m, below its minimum of {:.2} m",
+                room_id, dimension, actual, minimum
+            ),
+            Violation::AspectRatioOutOfRange { room_id, aspect_ratio, min_aspect, max_aspect } => write!(
+                f,
+                "Room {} aspect ratio {:.2} is outside [{}, {}]",
+                room_id,
+                aspect_ratio,
+                min_aspect.map_or("-".to_string(), |v| format!("{:.2}", v)),
+                max_aspect.map_or("-".to_string(), |v| format!("{:.2}", v))
+            ),
+            Violation::MissingExteriorWall { room_id } => {
+                write!(f, "Room {} does not touch the exterior wall", room_id)
+            }
+            Violation::NotOnRequiredSide { room_id, sides } => {
+                write!(f, "Room {} does not touch any of the boundary sides {:?}", room_id, sides)
+            }
+            Violation::InsufficientFacade { room_id, orientations, facade_length, min_facade_length } => write!(
+                f,
+                "Room {} has {:.2} m of facade facing {:?}, needs at least {:.2} m",
+                room_id, facade_length, orientations, min_facade_length
+            ),
+            Violation::MissingAdjacency { room_id, required_room_id } => write!(
+                f,
+                "Room {} is not adjacent to required room {}",
+                room_id, required_room_id
+            ),
+            Violation::ForbiddenAdjacency { room_id, forbidden_room_id, shared_edge_length } => write!(
+                f,
+                "Room {} is adjacent to forbidden room {} ({:.2} m shared wall)",
+                room_id, forbidden_room_id, shared_edge_length
+            ),
+            Violation::DistanceOutOfRange { room_id, other_room_id, metric, distance, min_distance, max_distance } => write!(
+                f,
+                "Room {} is {:.2} m from {} ({:?}), outside [{}, {}]",
+                room_id,
+                distance,
+                other_room_id,
+                metric,
+                min_distance.map_or("-".to_string(), |v| format!("{:.2}", v)),
+                max_distance.map_or("-".to_string(), |v| format!("{:.2}", v))
+            ),
+            Violation::RelativePositionViolated { room_id, other_room_id, position } => {
+                write!(f, "Room {} is not {:?} room {}", room_id, position, other_room_id)
+            }
+            Violation::Misaligned { room_id, other_room_id, alignment } => {
+                write!(f, "Room {} is not aligned ({:?}) with room {}", room_id, alignment, other_room_id)
+            }
+            Violation::ZoneNotContiguous { room_id, zone } => {
+                write!(f, "Room {} is cut off from the rest of the {:?} zone", room_id, zone)
+            }
+            Violation::MissingEntrance { room_id } => {
+                write!(f, "Room {} does not contain an entrance", room_id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test 1: test_exterior_wall_message_states_missing_contact
+    #[test]
+    fn test_exterior_wall_message_states_missing_contact() {
+        let violation = Violation::MissingExteriorWall { room_id: "living".to_string() };
+        assert_eq!(violation.to_string(), "Room living does not touch the exterior wall");
+    }
+
+    // Test 2: test_overlap_message_includes_area
+    #[test]
+    fn test_overlap_message_includes_area() {
+        let violation = Violation::Overlap {
+            room_id: "room1".to_string(),
+            other_room_id: "room2".to_string(),
+            overlap_area: 4.0,
+        };
+        assert_eq!(
+            violation.to_string(),
+            "Room room1 overlaps with already placed room room2 (4.00 m²)"
+        );
+    }
+
+    // Test 3: test_room_id_returns_offending_room
+    #[test]
+    fn test_room_id_returns_offending_room() {
+        let violation = Violation::ForbiddenAdjacency {
+            room_id: "kitchen".to_string(),
+            forbidden_room_id: "bedroom".to_string(),
+            shared_edge_length: 3.0,
+        };
+        assert_eq!(violation.room_id(), "kitchen");
+    }
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::Segment;
+use crate::solver::LayoutSolution;
+use crate::types::Room;
+
+/// What a wall separates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WallKind {
+    /// On the boundary, between a room and the outside.
+    Exterior,
+    /// Between two rooms.
+    InteriorShared,
+    /// Between a room and unassigned space, such as circulation.
+    InteriorFreeStanding,
+}
+
+/// A straight wall between two nodes of the wall graph.
+///
+/// Sides are named in plan coordinates: for a vertical wall the first side
+/// is the left one, for a horizontal wall it is the one above (smaller y).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wall {
+    pub segment: Segment,
+    /// Indices of the end points in `WallGraph::nodes`.
+    pub start: usize,
+    pub end: usize,
+    pub kind: WallKind,
+    /// Room on the left of a vertical wall or above a horizontal one.
+    pub left_or_above: Option<String>,
+    /// Room on the right of a vertical wall or below a horizontal one.
+    pub right_or_below: Option<String>,
+}
+
+/// Deduplicated walls of a layout and the points where they meet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WallGraph {
+    pub nodes: Vec<(f64, f64)>,
+    pub walls: Vec<Wall>,
+}
+
+/// Room edges lying on one grid line, as (start, end, room id, room lies after the line).
+type LineEdges<'a> = Vec<(f64, f64, &'a str, bool)>;
+
+/// Build the wall graph of a solved layout.
+///
+/// Every room edge becomes wall. Edges of two rooms that coincide become a
+/// single shared wall, and runs of wall along the same line separating the
+/// same rooms are merged into one segment. Circulation counts as unassigned
+/// space.
+pub fn extract_walls(solution: &LayoutSolution, boundary_width: f64, boundary_height: f64) -> WallGraph {
+    wall_graph(&solution.rooms, boundary_width, boundary_height)
+}
+
+/// Build the wall graph of a set of rooms; see `extract_walls`.
+pub fn wall_graph(rooms: &[Room], boundary_width: f64, boundary_height: f64) -> WallGraph {
+    // Vertical lines keyed by x, horizontal lines keyed by y
+    let mut vertical: Vec<(f64, LineEdges)> = Vec::new();
+    let mut horizontal: Vec<(f64, LineEdges)> = Vec::new();
+    for room in rooms {
+        let (left, right) = (room.x, room.x + room.width);
+        let (top, bottom) = (room.y, room.y + room.height);
+        add_edge(&mut vertical, left, (top, bottom, room.id.as_str(), true));
+        add_edge(&mut vertical, right, (top, bottom, room.id.as_str(), false));
+        add_edge(&mut horizontal, top, (left, right, room.id.as_str(), true));
+        add_edge(&mut horizontal, bottom, (left, right, room.id.as_str(), false));
+    }
+
+    let mut graph = WallGraph { nodes: Vec::new(), walls: Vec::new() };
+    for (x, edges) in vertical.iter() {
+        let on_boundary = *x == 0.0 || *x == boundary_width;
+        for (start, end, before, after) in merged_runs(edges) {
+            let segment = Segment { x1: *x, y1: start, x2: *x, y2: end };
+            graph.push_wall(segment, on_boundary, before, after);
+        }
+    }
+    for (y, edges) in horizontal.iter() {
+        let on_boundary = *y == 0.0 || *y == boundary_height;
+        for (start, end, before, after) in merged_runs(edges) {
+            let segment = Segment { x1: start, y1: *y, x2: end, y2: *y };
+            graph.push_wall(segment, on_boundary, before, after);
+        }
+    }
+    graph
+}
+
+impl WallGraph {
+    fn node(&mut self, point: (f64, f64)) -> usize {
+        match self.nodes.iter().position(|node| *node == point) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(point);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn push_wall(&mut self, segment: Segment, on_boundary: bool, before: Option<&str>, after: Option<&str>) {
+        let kind = if on_boundary {
+            WallKind::Exterior
+        } else if before.is_some() && after.is_some() {
+            WallKind::InteriorShared
+        } else {
+            WallKind::InteriorFreeStanding
+        };
+        let start = self.node((segment.x1, segment.y1));
+        let end = self.node((segment.x2, segment.y2));
+        self.walls.push(Wall {
+            segment,
+            start,
+            end,
+            kind,
+            left_or_above: before.map(str::to_string),
+            right_or_below: after.map(str::to_string),
+        });
+    }
+}
+
+fn add_edge<'a>(lines: &mut Vec<(f64, LineEdges<'a>)>, coordinate: f64, edge: (f64, f64, &'a str, bool)) {
+    match lines.iter_mut().find(|(c, _)| *c == coordinate) {
+        Some((_, edges)) => edges.push(edge),
+        None => lines.push((coordinate, vec![edge])),
+    }
+}
+
+/// Split the edges on a line at every end point, find the rooms on each
+/// side of each piece, and merge touching pieces with the same rooms.
+fn merged_runs<'a>(edges: &LineEdges<'a>) -> Vec<(f64, f64, Option<&'a str>, Option<&'a str>)> {
+    let mut breaks: Vec<f64> = edges.iter().flat_map(|(start, end, _, _)| [*start, *end]).collect();
+    breaks.sort_by(f64::total_cmp);
+    breaks.dedup();
+
+    let mut runs: Vec<(f64, f64, Option<&str>, Option<&str>)> = Vec::new();
+    for piece in breaks.windows(2) {
+        let (start, end) = (piece[0], piece[1]);
+        let covering = |room_after: bool| {
+            edges
+                .iter()
+                .find(|(s, e, _, after)| *after == room_after && *s <= start && *e >= end)
+                .map(|(_, _, room_id, _)| *room_id)
+        };
+        let (before, after) = (covering(false), covering(true));
+        if before.is_none() && after.is_none() {
+            continue;
+        }
+
+        match runs.last_mut() {
+            Some(run) if run.1 == start && run.2 == before && run.3 == after => run.1 = end,
+            _ => runs.push((start, end, before, after)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    // Test 1: test_shared_wall_deduplicated_and_classified
+    #[test]
+    fn test_shared_wall_deduplicated_and_classified() {
+        // Two rooms side by side filling the top of a 10 x 6 boundary
+        let rooms = vec![room("living", 0.0, 0.0, 6.0, 4.0), room("kitchen", 6.0, 0.0, 4.0, 4.0)];
+        let graph = wall_graph(&rooms, 10.0, 6.0);
+
+        let shared: Vec<&Wall> = graph.walls.iter().filter(|w| w.kind == WallKind::InteriorShared).collect();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].segment, Segment { x1: 6.0, y1: 0.0, x2: 6.0, y2: 4.0 });
+        assert_eq!(shared[0].left_or_above.as_deref(), Some("living"));
+        assert_eq!(shared[0].right_or_below.as_deref(), Some("kitchen"));
+
+        // The bottom edges face free space, one run per room above them
+        let free: Vec<&Wall> = graph.walls.iter().filter(|w| w.kind == WallKind::InteriorFreeStanding).collect();
+        assert_eq!(free.len(), 2);
+        assert!(free.iter().all(|w| w.segment.y1 == 4.0));
+
+        // Exterior: left, right, and one top wall per room
+        assert_eq!(graph.walls.iter().filter(|w| w.kind == WallKind::Exterior).count(), 4);
+        let node_points: Vec<(f64, f64)> =
+            graph.walls.iter().flat_map(|w| [graph.nodes[w.start], graph.nodes[w.end]]).collect();
+        assert!(node_points.contains(&(6.0, 4.0)));
+    }
+
+    // Test 2: test_collinear_walls_split_where_rooms_change
+    #[test]
+    fn test_collinear_walls_split_where_rooms_change() {
+        // hall spans under two rooms; its top wall splits where the rooms above change
+        let rooms = vec![
+            room("bed1", 0.0, 0.0, 3.0, 3.0),
+            room("bed2", 3.0, 0.0, 3.0, 3.0),
+            room("hall", 0.0, 3.0, 6.0, 1.0),
+        ];
+        let graph = wall_graph(&rooms, 6.0, 6.0);
+
+        let on_line: Vec<&Wall> = graph
+            .walls
+            .iter()
+            .filter(|w| w.segment.y1 == 3.0 && w.segment.y2 == 3.0)
+            .collect();
+        assert_eq!(on_line.len(), 2);
+        assert!(on_line.iter().all(|w| w.right_or_below.as_deref() == Some("hall")));
+
+        // The hall's bottom wall is a single free-standing run
+        let bottom: Vec<&Wall> = graph.walls.iter().filter(|w| w.segment.y1 == 4.0).collect();
+        assert_eq!(bottom.len(), 1);
+        assert_eq!(bottom[0].segment.length(), 6.0);
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::Rectangle;
+use crate::types::BoundarySide;
+
+/// Thickness of the walls between and around rooms, in meters.
+///
+/// With walls, a placed room is its gross rectangle: it reaches the centre
+/// line of each interior wall and the outside face of each exterior wall, so
+/// the boundary is the outside face of the building. Rooms that touch are
+/// then separated by exactly one interior wall, and size bounds apply to the
+/// net rectangle inside the walls.
+///
+/// Missing fields deserialize to their defaults, so JavaScript callers can
+/// override a single value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WallThickness {
+    pub interior: f64,
+    pub exterior: f64,
+}
+
+impl Default for WallThickness {
+    fn default() -> Self {
+        Self {
+            interior: 0.1,
+            exterior: 0.3,
+        }
+    }
+}
+
+impl WallThickness {
+    /// Net rectangle inside the walls of a gross room rectangle.
+    ///
+    /// Sides on the boundary lose the full exterior thickness; every other
+    /// side loses half of an interior wall.
+    pub fn net_rectangle(&self, gross: &Rectangle, boundary_width: f64, boundary_height: f64) -> Rectangle {
+        let inset = |side| {
+            if gross.touches_side(side, boundary_width, boundary_height) {
+                self.exterior
+            } else {
+                self.interior / 2.0
+            }
+        };
+        let (top, bottom) = (inset(BoundarySide::Top), inset(BoundarySide::Bottom));
+        let (left, right) = (inset(BoundarySide::Left), inset(BoundarySide::Right));
+
+        Rectangle {
+            x: gross.x + left,
+            y: gross.y + top,
+            width: (gross.width - left - right).max(0.0),
+            height: (gross.height - top - bottom).max(0.0),
+        }
+    }
+
+    /// Extra gross length a room side may need for its two walls, one per wall
+    /// combination, rounded up to `step` and without repeats.
+    pub fn allowances(&self, step: f64) -> Vec<f64> {
+        let combinations = [self.interior, self.interior / 2.0 + self.exterior, 2.0 * self.exterior];
+
+        let mut allowances: Vec<f64> = Vec::new();
+        for allowance in combinations {
+            let rounded = (allowance / step - 1e-9).ceil().max(0.0) * step;
+            if !allowances.contains(&rounded) {
+                allowances.push(rounded);
+            }
+        }
+        allowances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test 1: test_net_rectangle_insets_by_wall_type
+    #[test]
+    fn test_net_rectangle_insets_by_wall_type() {
+        let walls = WallThickness::default();
+
+        // Top-left corner room: exterior walls on the top and left
+        let corner = Rectangle { x: 0.0, y: 0.0, width: 4.0, height: 3.0 };
+        let net = walls.net_rectangle(&corner, 10.0, 8.0);
+        assert!((net.x - 0.3).abs() < 1e-9 && (net.y - 0.3).abs() < 1e-9);
+        assert!((net.width - 3.65).abs() < 1e-9 && (net.height - 2.65).abs() < 1e-9);
+
+        // Neighbours sharing a gross edge are one interior wall apart
+        let neighbour = Rectangle { x: 4.0, y: 0.0, width: 3.0, height: 3.0 };
+        let next = walls.net_rectangle(&neighbour, 10.0, 8.0);
+        assert!((next.x - (net.x + net.width) - walls.interior).abs() < 1e-9);
+    }
+
+    // Test 2: test_allowances_rounded_to_grid
+    #[test]
+    fn test_allowances_rounded_to_grid() {
+        assert_eq!(WallThickness::default().allowances(0.5), vec![0.5, 1.0]);
+        assert_eq!(WallThickness { interior: 0.0, exterior: 0.0 }.allowances(0.5), vec![0.0]);
+    }
+}
@@ -7,14 +7,32 @@
 
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
-use crate::solver::solve_layout as solve_layout_internal;
-use crate::types::RoomRequirement;
+use crate::adjacency_graph::{adjacency_report as adjacency_report_internal, AdjacencyReport};
+use crate::annealing::AnnealingSettings;
+use crate::config::{SolverConfig, SolverStrategy};
+use crate::doors::DoorSettings;
+use crate::walls::WallThickness;
+use crate::windows::WindowSettings;
+use crate::free_space::{FreeRectangle, FreeSpace};
+use crate::instances::expand_instances;
+use crate::scoring::ScoringWeights;
+use crate::solver::{solve_layout_with_config, SolverError};
+use crate::types::{
+    AdjacencyRelation, AdjacencyStrength, AlignmentGroup, BoundarySide, DistanceConstraint, Entrance, MirrorGroup,
+    Orientation, PositionConstraint, Room, RoomRequirement, Zone, ZoneContiguity,
+};
+use crate::validation::{
+    validate_config, validate_entrances, validate_requirements as validate_requirements_internal, ValidationError,
+};
 use instant::Instant;
 
 /// JavaScript-compatible input structure for room requirements.
 ///
 /// This structure is deserialized from JavaScript objects and converted
-/// to internal `RoomRequirement` types.
+/// to internal `RoomRequirement` types. Size bounds, weighted `relations`,
+/// `distance_constraints`, `position_constraints`, `exterior_sides`, the
+/// facade requirements, `glazing_ratio`, `zone`, `contains_entrance` and the count and priority fields are
+/// optional and may be omitted from the JavaScript object.
 #[derive(Serialize, Deserialize)]
 pub struct RoomInput {
     pub id: String,
@@ -22,6 +40,107 @@ pub struct RoomInput {
     pub adjacent_to: Vec<String>,
     pub not_adjacent_to: Vec<String>,
     pub has_exterior_wall: bool,
+    #[serde(default)]
+    pub max_area: Option<f64>,
+    #[serde(default)]
+    pub min_width: Option<f64>,
+    #[serde(default)]
+    pub min_depth: Option<f64>,
+    #[serde(default)]
+    pub min_aspect: Option<f64>,
+    #[serde(default)]
+    pub max_aspect: Option<f64>,
+    #[serde(default)]
+    pub relations: Vec<RelationInput>,
+    #[serde(default)]
+    pub distance_constraints: Vec<DistanceConstraint>,
+    #[serde(default)]
+    pub position_constraints: Vec<PositionConstraint>,
+    #[serde(default)]
+    pub exterior_sides: Vec<BoundarySide>,
+    #[serde(default)]
+    pub facade_orientations: Vec<Orientation>,
+    #[serde(default)]
+    pub min_facade_length: Option<f64>,
+    #[serde(default)]
+    pub glazing_ratio: Option<f64>,
+    #[serde(default)]
+    pub zone: Option<Zone>,
+    #[serde(default)]
+    pub contains_entrance: bool,
+    #[serde(default)]
+    pub min_count: Option<usize>,
+    #[serde(default)]
+    pub max_count: Option<usize>,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub priority: u32,
+}
+
+/// JavaScript-compatible input structure for a weighted adjacency relation.
+///
+/// `strength` is one of `"required"`, `"strongly_preferred"`, `"preferred"`,
+/// `"avoid"` or `"forbidden"`. When `weight` is omitted the strength's
+/// default weight is used.
+#[derive(Serialize, Deserialize)]
+pub struct RelationInput {
+    pub room_id: String,
+    pub strength: AdjacencyStrength,
+    #[serde(default)]
+    pub weight: Option<f64>,
+}
+
+/// JavaScript-compatible solver options.
+///
+/// Every field is optional; omitted fields keep the solver defaults.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SolverOptionsInput {
+    pub weights: ScoringWeights,
+    /// Clockwise angle in degrees from the boundary's top side to true north;
+    /// set here because the boundary itself is only `boundary_width` and `boundary_height`.
+    pub north_angle: f64,
+    pub alignment_groups: Vec<AlignmentGroup>,
+    pub mirror_groups: Vec<MirrorGroup>,
+    pub entrances: Vec<Entrance>,
+    pub zone_contiguity: Vec<ZoneContiguity>,
+    pub fill: bool,
+    pub corridor_width: Option<f64>,
+    pub require_reachability: bool,
+    pub doors: DoorSettings,
+    pub windows: WindowSettings,
+    pub walls: Option<WallThickness>,
+    pub strategy: SolverStrategy,
+    pub annealing: AnnealingSettings,
+}
+
+/// Parses optional solver options, treating `undefined`/`null` as defaults.
+fn to_solver_config(options: JsValue) -> Result<SolverConfig, JsValue> {
+    let options: SolverOptionsInput = if options.is_undefined() || options.is_null() {
+        SolverOptionsInput::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?
+    };
+
+    Ok(SolverConfig {
+        weights: options.weights,
+        north_angle: options.north_angle,
+        alignment_groups: options.alignment_groups,
+        mirror_groups: options.mirror_groups,
+        entrances: options.entrances,
+        zone_contiguity: options.zone_contiguity,
+        fill: options.fill,
+        corridor_width: options.corridor_width,
+        require_reachability: options.require_reachability,
+        doors: options.doors,
+        windows: options.windows,
+        walls: options.walls,
+        strategy: options.strategy,
+        annealing: options.annealing,
+        ..Default::default()
+    })
 }
 
 /// JavaScript-compatible output structure for layout solutions.
@@ -31,13 +150,48 @@ pub struct RoomInput {
 #[derive(Serialize, Deserialize)]
 pub struct SolutionOutput {
     pub rooms: Vec<PlacedRoomOutput>,
+    pub dropped_rooms: Vec<String>,
+    pub unreachable_rooms: Vec<String>,
+    pub circulation: Vec<PlacedRoomOutput>,
+    pub doors: Vec<DoorOutput>,
+    pub blocked_doors: Vec<(String, String)>,
+    pub windows: Vec<WindowOutput>,
+    pub underglazed_rooms: Vec<String>,
+    pub net_to_gross: f64,
+    pub free_space: FreeSpaceOutput,
     pub score: f64,
     pub computation_time_ms: u64,
 }
 
+/// JavaScript-compatible summary of the unused parts of the boundary.
+///
+/// `rectangles` are the maximal free rectangles (they may overlap) and
+/// `largest` is the biggest of them, or `null` when the boundary is full.
+#[derive(Serialize, Deserialize)]
+pub struct FreeSpaceOutput {
+    pub rectangles: Vec<FreeRectangleOutput>,
+    pub total_area: f64,
+    pub largest: Option<FreeRectangleOutput>,
+    pub is_connected: bool,
+}
+
+/// JavaScript-compatible structure for a free rectangle and the rooms it touches.
+#[derive(Serialize, Deserialize)]
+pub struct FreeRectangleOutput {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub touching_rooms: Vec<String>,
+}
+
 /// JavaScript-compatible structure for a placed room.
 ///
-/// Represents a room with its final position and dimensions.
+/// Represents a room with its final position and dimensions. `x`, `y`,
+/// `width` and `height` are gross, to the wall centre lines and the outside
+/// face; the `net_` fields are inside the walls (the same as gross when
+/// walls are not modelled). `mirror_of` names the source room when this room
+/// was derived by mirroring.
 #[derive(Serialize, Deserialize)]
 pub struct PlacedRoomOutput {
     pub id: String,
@@ -45,6 +199,156 @@ pub struct PlacedRoomOutput {
     pub y: f64,
     pub width: f64,
     pub height: f64,
+    pub net_x: f64,
+    pub net_y: f64,
+    pub net_width: f64,
+    pub net_height: f64,
+    pub mirror_of: Option<String>,
+}
+
+/// JavaScript-compatible structure for a door between two rooms.
+///
+/// The opening runs from (`x1`, `y1`) to (`x2`, `y2`) along the shared wall.
+#[derive(Serialize, Deserialize)]
+pub struct DoorOutput {
+    pub room_id: String,
+    pub other_room_id: String,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub width: f64,
+}
+
+/// JavaScript-compatible structure for a window on an exterior wall.
+///
+/// The opening runs from (`x1`, `y1`) to (`x2`, `y2`) along the boundary;
+/// `orientation` is the compass direction it faces.
+#[derive(Serialize, Deserialize)]
+pub struct WindowOutput {
+    pub room_id: String,
+    pub side: BoundarySide,
+    pub orientation: Orientation,
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+    pub width: f64,
+    pub area: f64,
+}
+
+/// JavaScript-compatible input structure for a room already placed in a layout.
+///
+/// Other fields are ignored, so the `rooms` of a `solve_layout` result can be
+/// passed back in as they are.
+#[derive(Serialize, Deserialize)]
+pub struct PlacedRoomInput {
+    pub id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// JavaScript-compatible structure for a layout's adjacency report.
+///
+/// `dot` and `json` are ready-made Graphviz and JSON renderings of the same
+/// report for design reviews.
+#[derive(Serialize)]
+pub struct AdjacencyReportOutput {
+    #[serde(flatten)]
+    pub report: AdjacencyReport,
+    pub dot: String,
+    pub json: String,
+}
+
+/// JavaScript-compatible structure for a requirement validation problem.
+///
+/// Carries the typed error fields (tagged by `kind`) alongside a
+/// human-readable message for display next to the offending form field.
+#[derive(Serialize)]
+pub struct ValidationErrorOutput {
+    #[serde(flatten)]
+    pub error: ValidationError,
+    pub message: String,
+}
+
+/// JavaScript-compatible structure for a solver failure, thrown by `solve_layout`.
+///
+/// `kind` names the failure and `details` carries its typed fields: the
+/// validation errors, the infeasibility or the rectangular dual error.
+#[derive(Serialize)]
+pub struct SolverErrorOutput {
+    #[serde(flatten)]
+    pub error: SolverError,
+    pub message: String,
+}
+
+/// Serializes a value for JavaScript, with maps (including flattened
+/// structs) as plain objects rather than `Map`s.
+fn to_js_value<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+        .map_err(|e| JsValue::from_str(&format!("Serialize error: {}", e)))
+}
+
+/// Converts a free space analysis to its JavaScript-compatible form.
+fn to_free_space_output(free_space: &FreeSpace) -> FreeSpaceOutput {
+    let rectangle = |free: &FreeRectangle| FreeRectangleOutput {
+        x: free.rect.x,
+        y: free.rect.y,
+        width: free.rect.width,
+        height: free.rect.height,
+        touching_rooms: free.touching_room_ids.clone(),
+    };
+
+    FreeSpaceOutput {
+        rectangles: free_space.rectangles.iter().map(rectangle).collect(),
+        total_area: free_space.total_area,
+        largest: free_space
+            .rectangles
+            .iter()
+            .find(|free| Some(free.rect) == free_space.largest)
+            .map(rectangle),
+        is_connected: free_space.is_connected,
+    }
+}
+
+/// Converts parsed JavaScript room inputs to internal requirements.
+fn to_room_requirements(room_inputs: &[RoomInput]) -> Vec<RoomRequirement> {
+    room_inputs.iter().map(|input| {
+        RoomRequirement {
+            id: input.id.clone(),
+            min_area: input.min_area,
+            adjacent_to: input.adjacent_to.clone(),
+            not_adjacent_to: input.not_adjacent_to.clone(),
+            has_exterior_wall: input.has_exterior_wall,
+            max_area: input.max_area,
+            min_width: input.min_width,
+            min_depth: input.min_depth,
+            min_aspect: input.min_aspect,
+            max_aspect: input.max_aspect,
+            relations: input.relations.iter().map(|relation| {
+                AdjacencyRelation {
+                    room_id: relation.room_id.clone(),
+                    strength: relation.strength,
+                    weight: relation.weight.unwrap_or_else(|| relation.strength.default_weight()),
+                }
+            }).collect(),
+            distance_constraints: input.distance_constraints.clone(),
+            position_constraints: input.position_constraints.clone(),
+            exterior_sides: input.exterior_sides.clone(),
+            facade_orientations: input.facade_orientations.clone(),
+            min_facade_length: input.min_facade_length,
+            glazing_ratio: input.glazing_ratio,
+            zone: input.zone,
+            contains_entrance: input.contains_entrance,
+            min_count: input.min_count,
+            max_count: input.max_count,
+            optional: input.optional,
+            priority: input.priority,
+        }
+    }).collect()
 }
 
 /// Solves the room layout problem from JavaScript.
@@ -57,11 +361,32 @@ pub struct PlacedRoomOutput {
 /// * `rooms_json` - JavaScript array of room requirement objects
 /// * `boundary_width` - Width of the layout boundary in meters
 /// * `boundary_height` - Height of the layout boundary in meters
+/// * `options` - Optional solver options, e.g. `{ north_angle: 90.0, weights: { exterior_wall_bonus: 6.0 } }`;
+///   `alignment_groups` takes `{ room_ids, alignment, hard, weight }` objects and
+///   `mirror_groups` takes `{ room_ids, mirrored_ids, axis }` objects; `entrances` takes
+///   `{ x, y }` points, `zone_contiguity` takes `{ zone, hard, weight }` objects and
+///   `fill: true` grows rooms to cover the boundary; `corridor_width` adds corridors from the
+///   entrances so every room is reachable; `require_reachability: true` instead requires a
+///   route from an entrance through rooms that may open onto each other; `doors` takes
+///   `{ width, min_width, corner_clearance }`, `windows` takes `{ height, corner_clearance }` and
+///   `walls` takes `{ interior, exterior }` thicknesses; `strategy` is `"backtracking"` (the default),
+///   `"rectangular_dual"`, which realises every required adjacency in a gap-free floorplan,
+///   `"slicing_tree"`, which anneals over guillotine floorplans, or `"sequence_pair"`, which anneals
+///   over compact packings; both are tuned by `annealing: { iterations, initial_temperature, cooling_rate, seed }`
 ///
 /// # Returns
 ///
 /// A JavaScript object containing:
-/// - `rooms`: Array of placed rooms with positions and dimensions
+/// - `rooms`: Array of placed rooms with gross and net positions and dimensions, and `mirror_of`
+/// - `dropped_rooms`: Ids of optional room instances that did not fit
+/// - `unreachable_rooms`: Ids of rooms with no route from an entrance
+/// - `doors`: One door per required connection, as a segment on the shared wall
+/// - `blocked_doors`: `[room_id, other_room_id]` pairs whose shared wall is too short for a door
+/// - `windows`: Windows on exterior walls with their facing direction and glazed area
+/// - `underglazed_rooms`: Ids of rooms whose exterior walls cannot meet their `glazing_ratio`
+/// - `circulation`: Corridors (`corridor_N`) and leftover space (`circulation_N`) as rectangles
+/// - `net_to_gross`: Net room area over boundary area
+/// - `free_space`: Maximal free rectangles, total and largest free area, and connectivity
 /// - `score`: Total quality score of the solution
 /// - `computation_time_ms`: Time taken to solve in milliseconds
 ///
@@ -69,9 +394,14 @@ pub struct PlacedRoomOutput {
 ///
 /// Returns a JavaScript error if:
 /// - Input cannot be parsed
+/// - Room requirements fail validation (see `validate_requirements`)
 /// - No valid solution exists
 /// - Output cannot be serialized
 ///
+/// Solver failures are thrown as objects with a `kind` tag
+/// (`invalid_requirements`, `infeasible`, `no_rectangular_dual` or
+/// `no_solution_found`), its `details` and a `message` string.
+///
 /// # JavaScript Example
 ///
 /// ```javascript
@@ -115,39 +445,88 @@ pub fn solve_layout(
     rooms_json: JsValue,
     boundary_width: f64,
     boundary_height: f64,
+    options: JsValue,
 ) -> Result<JsValue, JsValue> {
     // 1. Deserialize input from JS
     let room_inputs: Vec<RoomInput> = serde_wasm_bindgen::from_value(rooms_json)
         .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+    let config = to_solver_config(options)?;
     
     // 2. Convert to your internal RoomRequirement types
-    let rooms: Vec<RoomRequirement> = room_inputs.iter().map(|input| {
-        RoomRequirement {
-            id: input.id.clone(),
-            min_area: input.min_area,
-            adjacent_to: input.adjacent_to.clone(),
-            not_adjacent_to: input.not_adjacent_to.clone(),
-            has_exterior_wall: input.has_exterior_wall,
-        }
-    }).collect();
+    let rooms = to_room_requirements(&room_inputs);
     
     // 3. Call your existing solver
     let start = Instant::now();
-    let solution = solve_layout_internal(rooms, boundary_width, boundary_height)
-        .map_err(|e| JsValue::from_str(&format!("Solver error: {:?}", e)))?;
+    let solution = solve_layout_with_config(rooms, boundary_width, boundary_height, &config)
+        .map_err(|error| {
+            to_js_value(&SolverErrorOutput { message: error.to_string(), error }).unwrap_or_else(|e| e)
+        })?;
     let elapsed = start.elapsed().as_millis() as u64;
     
     // 4. Convert solution to JS-friendly format
     let output = SolutionOutput {
-        rooms: solution.rooms.iter().map(|room| {
+        rooms: solution.rooms.iter().zip(solution.net_rooms.iter()).map(|(room, net)| {
             PlacedRoomOutput {
                 id: room.id.clone(),
                 x: room.x,
                 y: room.y,
                 width: room.width,
                 height: room.height,
+                net_x: net.x,
+                net_y: net.y,
+                net_width: net.width,
+                net_height: net.height,
+                mirror_of: solution
+                    .mirrored_rooms
+                    .iter()
+                    .find(|mirrored| mirrored.room_id == room.id)
+                    .map(|mirrored| mirrored.source_id.clone()),
+            }
+        }).collect(),
+        dropped_rooms: solution.dropped_rooms.clone(),
+        unreachable_rooms: solution.unreachable_rooms.clone(),
+        circulation: solution.circulation.iter().map(|room| {
+            PlacedRoomOutput {
+                id: room.id.clone(),
+                x: room.x,
+                y: room.y,
+                width: room.width,
+                height: room.height,
+                net_x: room.x,
+                net_y: room.y,
+                net_width: room.width,
+                net_height: room.height,
+                mirror_of: None,
+            }
+        }).collect(),
+        doors: solution.doors.iter().map(|door| {
+            DoorOutput {
+                room_id: door.room_id.clone(),
+                other_room_id: door.other_room_id.clone(),
+                x1: door.segment.x1,
+                y1: door.segment.y1,
+                x2: door.segment.x2,
+                y2: door.segment.y2,
+                width: door.width(),
             }
         }).collect(),
+        blocked_doors: solution.blocked_doors.clone(),
+        windows: solution.windows.iter().map(|window| {
+            WindowOutput {
+                room_id: window.room_id.clone(),
+                side: window.side,
+                orientation: window.orientation,
+                x1: window.segment.x1,
+                y1: window.segment.y1,
+                x2: window.segment.x2,
+                y2: window.segment.y2,
+                width: window.width(),
+                area: window.area(),
+            }
+        }).collect(),
+        underglazed_rooms: solution.underglazed_rooms.clone(),
+        net_to_gross: solution.net_to_gross,
+        free_space: to_free_space_output(&solution.free_space),
         score: solution.total_score,
         computation_time_ms: elapsed,
     };
@@ -155,4 +534,110 @@ pub fn solve_layout(
     // 5. Serialize back to JS
     serde_wasm_bindgen::to_value(&output)
         .map_err(|e| JsValue::from_str(&format!("Serialize error: {}", e)))
-}
\ No newline at end of file
+}
+
+/// Validates room requirements from JavaScript without solving.
+///
+/// Runs the same checks `solve_layout` performs before searching, so a form
+/// can report every problem up front. As in `solve_layout`, the options
+/// (alignment and mirror groups, corridors, walls and so on) are checked once
+/// the requirements themselves are valid, and entrances and corridors are
+/// checked against the boundary.
+///
+/// # Arguments
+///
+/// * `rooms_json` - JavaScript array of room requirement objects
+/// * `boundary_width` - Width of the building boundary in meters
+/// * `boundary_height` - Height of the building boundary in meters
+/// * `options` - Optional solver options, as for `solve_layout`
+///
+/// # Returns
+///
+/// A JavaScript array of validation errors, empty when the requirements are
+/// valid. Each entry has a `kind` tag, the fields of that error and a
+/// `message` string.
+///
+/// # Errors
+///
+/// Returns a JavaScript error if the input cannot be parsed or the result
+/// cannot be serialized.
+#[wasm_bindgen]
+pub fn validate_requirements(
+    rooms_json: JsValue,
+    boundary_width: f64,
+    boundary_height: f64,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let room_inputs: Vec<RoomInput> = serde_wasm_bindgen::from_value(rooms_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+    let config = to_solver_config(options)?;
+
+    let rooms = to_room_requirements(&room_inputs);
+    let result = validate_requirements_internal(&rooms)
+        .and_then(|()| validate_entrances(&config.entrances, boundary_width, boundary_height))
+        .and_then(|()| {
+            let instances = expand_instances(&rooms);
+            validate_requirements_internal(&instances)
+                .and_then(|()| validate_config(&instances, &config, boundary_width, boundary_height))
+        });
+
+    let errors: Vec<ValidationErrorOutput> = match result {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.into_iter().map(|error| {
+            ValidationErrorOutput {
+                message: error.to_string(),
+                error,
+            }
+        }).collect(),
+    };
+
+    to_js_value(&errors)
+}
+
+/// Compares the adjacency graph of a placed layout with the requested one.
+///
+/// # Arguments
+///
+/// * `rooms_json` - JavaScript array of room requirement objects
+/// * `placed_rooms_json` - JavaScript array of placed rooms with `id`, `x`,
+///   `y`, `width` and `height`, such as `solution.rooms`
+///
+/// # Returns
+///
+/// A JavaScript object with:
+/// - `graph`: `nodes` (room ids) and `edges` (`room_id`, `other_room_id`,
+///   `shared_wall_length`) for every pair of rooms sharing a wall
+/// - `satisfied`, `missing`: requested adjacencies the layout does and does
+///   not realise
+/// - `forbidden_present`: avoided or forbidden pairs that share a wall
+/// - `dot`, `json`: the report rendered as Graphviz DOT and as JSON
+///
+/// # Errors
+///
+/// Returns a JavaScript error if the input cannot be parsed or the result
+/// cannot be serialized.
+#[wasm_bindgen]
+pub fn adjacency_report(rooms_json: JsValue, placed_rooms_json: JsValue) -> Result<JsValue, JsValue> {
+    let room_inputs: Vec<RoomInput> = serde_wasm_bindgen::from_value(rooms_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+    let placed_inputs: Vec<PlacedRoomInput> = serde_wasm_bindgen::from_value(placed_rooms_json)
+        .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+    let placed: Vec<Room> = placed_inputs.into_iter().map(|room| {
+        Room {
+            id: room.id,
+            x: room.x,
+            y: room.y,
+            width: room.width,
+            height: room.height,
+        }
+    }).collect();
+    let report = adjacency_report_internal(&placed, &to_room_requirements(&room_inputs));
+
+    let output = AdjacencyReportOutput {
+        dot: report.to_dot(),
+        json: report.to_json(),
+        report,
+    };
+    to_js_value(&output)
+}
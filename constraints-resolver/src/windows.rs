@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::SolverConfig;
+use crate::geometry::{Rectangle, Segment};
+use crate::types::{BoundarySide, Orientation, Room, RoomRequirement};
+
+/// Window sizes used when glazing exterior walls.
+///
+/// Missing fields deserialize to their defaults, so JavaScript callers can
+/// override a single value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    /// Height of every window; glazed area is window width times this height.
+    pub height: f64,
+    /// Wall kept clear between a window and either end of the exterior wall.
+    pub corner_clearance: f64,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            height: 1.2,
+            corner_clearance: 0.3,
+        }
+    }
+}
+
+/// A window on a room's exterior wall.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub room_id: String,
+    pub side: BoundarySide,
+    /// Compass direction the window faces, from the site orientation.
+    pub orientation: Orientation,
+    /// Window opening, lying on the boundary.
+    pub segment: Segment,
+    pub height: f64,
+}
+
+impl Window {
+    /// Width of the window opening.
+    pub fn width(&self) -> f64 {
+        self.segment.length()
+    }
+
+    /// Glazed area of the window.
+    pub fn area(&self) -> f64 {
+        self.width() * self.height
+    }
+}
+
+/// Windows placed for a layout and the rooms left short of their glazing ratio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowPlan {
+    pub windows: Vec<Window>,
+    /// Rooms whose exterior walls are too short for the glazing they need.
+    pub underglazed_room_ids: Vec<String>,
+}
+
+/// Glaze the exterior walls of every room with a `glazing_ratio`.
+///
+/// A room needs `glazing_ratio` times its net floor area of glass. Its
+/// exterior walls are glazed in turn, those facing one of its
+/// `facade_orientations` first and then the longest, each with at most one
+/// window centred on the wall and kept `config.windows.corner_clearance`
+/// away from both ends. Windows are only as wide as the remaining need.
+pub fn place_windows(
+    rooms: &[Room],
+    room_requirements: &[RoomRequirement],
+    boundary_width: f64,
+    boundary_height: f64,
+    config: &SolverConfig,
+) -> WindowPlan {
+    let (north_angle, settings) = (config.north_angle, &config.windows);
+    let mut windows = Vec::new();
+    let mut underglazed_room_ids = Vec::new();
+
+    for room in rooms {
+        let Some(room_req) = room_requirements.iter().find(|r| r.id == room.id) else { continue };
+        let Some(glazing_ratio) = room_req.glazing_ratio else { continue };
+
+        let rect = Rectangle::from_room(room);
+        let mut walls: Vec<(BoundarySide, Segment)> = BoundarySide::ALL
+            .iter()
+            .filter_map(|side| rect.exterior_contact(*side, boundary_width, boundary_height).map(|wall| (*side, wall)))
+            .collect();
+        let is_preferred = |side: &BoundarySide| room_req.facade_orientations.contains(&side.orientation(north_angle));
+        walls.sort_by(|(a_side, a_wall), (b_side, b_wall)| {
+            is_preferred(b_side)
+                .cmp(&is_preferred(a_side))
+                .then(b_wall.length().total_cmp(&a_wall.length()))
+        });
+
+        let net = config.net_room(room, boundary_width, boundary_height);
+        let mut needed_width = glazing_ratio * net.width * net.height / settings.height;
+        for (side, wall) in walls {
+            if needed_width <= 1e-9 {
+                break;
+            }
+            let usable = wall.length() - 2.0 * settings.corner_clearance;
+            if usable <= 0.0 {
+                continue;
+            }
+
+            let width = needed_width.min(usable);
+            windows.push(Window {
+                room_id: room.id.clone(),
+                side,
+                orientation: side.orientation(north_angle),
+                segment: centred_opening(&wall, width),
+                height: settings.height,
+            });
+            needed_width -= width;
+        }
+
+        if needed_width > 1e-9 {
+            underglazed_room_ids.push(room.id.clone());
+        }
+    }
+
+    WindowPlan { windows, underglazed_room_ids }
+}
+
+/// An opening of the given width centred on a wall.
+fn centred_opening(wall: &Segment, width: f64) -> Segment {
+    let length = wall.length();
+    let (mid_x, mid_y) = wall.midpoint();
+    let (unit_x, unit_y) = ((wall.x2 - wall.x1) / length, (wall.y2 - wall.y1) / length);
+    let half = width / 2.0;
+    Segment {
+        x1: mid_x - unit_x * half,
+        y1: mid_y - unit_y * half,
+        x2: mid_x + unit_x * half,
+        y2: mid_y + unit_y * half,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glazed(id: &str, glazing_ratio: f64, facade_orientations: Vec<Orientation>) -> RoomRequirement {
+        RoomRequirement {
+            id: id.to_string(),
+            min_area: 1.0,
+            glazing_ratio: Some(glazing_ratio),
+            facade_orientations,
+            ..Default::default()
+        }
+    }
+
+    // Test 1: test_window_sized_by_glazing_ratio_on_preferred_facade
+    #[test]
+    fn test_window_sized_by_glazing_ratio_on_preferred_facade() {
+        // Corner room: 6 m of wall on the top (north) and 4 m on the left (west)
+        let rooms = vec![Room { id: "living".to_string(), x: 0.0, y: 0.0, width: 6.0, height: 4.0 }];
+        let room_requirements = vec![glazed("living", 0.15, vec![Orientation::West])];
+
+        let plan = place_windows(&rooms, &room_requirements, 10.0, 8.0, &SolverConfig::default());
+
+        assert!(plan.underglazed_room_ids.is_empty());
+        assert_eq!(plan.windows.len(), 1);
+        let window = &plan.windows[0];
+        assert_eq!((window.side, window.orientation), (BoundarySide::Left, Orientation::West));
+        assert!((window.area() - 0.15 * 24.0).abs() < 1e-9);
+        assert!((window.segment.y1 - 0.5).abs() < 1e-9 && (window.segment.y2 - 3.5).abs() < 1e-9);
+    }
+
+    // Test 2: test_glazing_spills_to_other_walls_and_reports_shortfall
+    #[test]
+    fn test_glazing_spills_to_other_walls_and_reports_shortfall() {
+        let rooms = vec![
+            Room { id: "living".to_string(), x: 0.0, y: 0.0, width: 6.0, height: 4.0 },
+            Room { id: "hall".to_string(), x: 6.0, y: 0.0, width: 1.0, height: 6.0 },
+        ];
+        let room_requirements = vec![glazed("living", 0.3, vec![]), glazed("hall", 0.5, vec![])];
+
+        let plan = place_windows(&rooms, &room_requirements, 10.0, 8.0, &SolverConfig::default());
+
+        // Living needs 6 m of window: 5.4 m fits on the top wall and the rest on the left
+        let living: Vec<&Window> = plan.windows.iter().filter(|w| w.room_id == "living").collect();
+        assert_eq!(living.iter().map(|w| w.side).collect::<Vec<_>>(), vec![BoundarySide::Top, BoundarySide::Left]);
+        assert!((living.iter().map(|w| w.width()).sum::<f64>() - 6.0).abs() < 1e-9);
+        // Hall has only 1 m of exterior wall, 0.4 m once both corners are cleared
+        assert_eq!(plan.underglazed_room_ids, vec!["hall".to_string()]);
+    }
+}
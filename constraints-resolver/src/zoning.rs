@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use crate::config::SolverConfig;
+use crate::geometry::{self, Rectangle};
+use crate::types::{Room, RoomRequirement, Zone};
+use crate::violation::Violation;
+
+/// Zone of every zoned room, keyed by room id.
+pub fn room_zones(room_requirements: &[RoomRequirement]) -> HashMap<String, Zone> {
+    room_requirements
+        .iter()
+        .filter_map(|room_req| room_req.zone.map(|zone| (room_req.id.clone(), zone)))
+        .collect()
+}
+
+/// Check if the rooms form a single cluster in the adjacency graph.
+///
+/// Two rooms are connected when `is_adjacent_to` holds for them; an empty
+/// set counts as connected.
+pub fn is_connected(rooms: &[&Room]) -> bool {
+    let rects: Vec<Rectangle> = rooms.iter().map(|room| Rectangle::from_room(room)).collect();
+    geometry::is_connected(&rects)
+}
+
+/// Check if the placed rooms of a zone form a single cluster.
+pub fn is_zone_contiguous(zone: Zone, rooms: &[Room], room_zones: &HashMap<String, Zone>) -> bool {
+    let zone_rooms: Vec<&Room> = rooms.iter().filter(|room| room_zones.get(&room.id) == Some(&zone)).collect();
+    is_connected(&zone_rooms)
+}
+
+/// Check a placement against the contiguity constraints for its zone.
+///
+/// Soft constraints add their weight when the room shares a wall with a
+/// room of its zone already placed; the first room of a zone has nothing to
+/// touch and earns nothing. Hard constraints are checked once every room of
+/// the zone is placed, on the whole zone as in `is_zone_contiguous`, so a
+/// later room may still join two parts of it. Zones left incomplete, such as
+/// by dropped optional rooms, are checked by the solver on the finished layout.
+///
+/// Returns the soft score earned, or the violation when a hard constraint
+/// is broken.
+pub fn check_zone_contiguity(
+    placed_room: &Room,
+    already_placed: &[Room],
+    room_zones: &HashMap<String, Zone>,
+    config: &SolverConfig,
+) -> Result<f64, Violation> {
+    let Some(&zone) = room_zones.get(&placed_room.id) else { return Ok(0.0) };
+
+    let room_rect = Rectangle::from_room(placed_room);
+    let touches_zone = already_placed
+        .iter()
+        .filter(|room| room_zones.get(&room.id) == Some(&zone))
+        .any(|room| room_rect.is_adjacent_to(&Rectangle::from_room(room)));
+    let is_placed = |room_id: &String| *room_id == placed_room.id || already_placed.iter().any(|r| r.id == *room_id);
+    let zone_complete = room_zones.iter().filter(|(_, room_zone)| **room_zone == zone).all(|(id, _)| is_placed(id));
+
+    let mut score = 0.0;
+    for constraint in config.zone_contiguity.iter().filter(|c| c.zone == zone) {
+        if !constraint.hard {
+            score += if touches_zone { constraint.weight } else { 0.0 };
+            continue;
+        }
+        if zone_complete {
+            let mut rooms = already_placed.to_vec();
+            rooms.push(placed_room.clone());
+            if !is_zone_contiguous(zone, &rooms, room_zones) {
+                return Err(Violation::ZoneNotContiguous { room_id: placed_room.id.clone(), zone });
+            }
+        }
+    }
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ZoneContiguity;
+
+    fn room(id: &str, x: f64, y: f64, width: f64, height: f64) -> Room {
+        Room { id: id.to_string(), x, y, width, height }
+    }
+
+    fn zones(entries: &[(&str, Zone)]) -> HashMap<String, Zone> {
+        entries.iter().map(|(id, zone)| (id.to_string(), *zone)).collect()
+    }
+
+    // Test 1: test_zone_contiguous_through_shared_walls
+    #[test]
+    fn test_zone_contiguous_through_shared_walls() {
+        let rooms = vec![
+            room("bed1", 0.0, 0.0, 3.0, 3.0),
+            room("bed2", 3.0, 0.0, 3.0, 3.0),
+            room("living", 6.0, 0.0, 4.0, 3.0),
+            room("bed3", 10.0, 0.0, 3.0, 3.0),
+        ];
+        let room_zones = zones(&[
+            ("bed1", Zone::Private),
+            ("bed2", Zone::Private),
+            ("living", Zone::Public),
+            ("bed3", Zone::Private),
+        ]);
+
+        // bed3 is cut off from the other bedrooms by the living room
+        assert!(!is_zone_contiguous(Zone::Private, &rooms, &room_zones));
+        assert!(is_zone_contiguous(Zone::Private, &rooms[..3], &room_zones));
+        assert!(is_zone_contiguous(Zone::Public, &rooms, &room_zones));
+    }
+
+    // Test 2: test_check_zone_contiguity_hard_and_soft
+    #[test]
+    fn test_check_zone_contiguity_hard_and_soft() {
+        let placed = vec![room("bed1", 0.0, 0.0, 3.0, 3.0)];
+        let touching = room("bed2", 3.0, 0.0, 3.0, 3.0);
+        let apart = room("bed2", 6.0, 0.0, 3.0, 3.0);
+        let room_zones = zones(&[("bed1", Zone::Private), ("bed2", Zone::Private)]);
+        let constraint = |hard| ZoneContiguity { zone: Zone::Private, hard, weight: 2.0 };
+
+        let hard = SolverConfig { zone_contiguity: vec![constraint(true)], ..Default::default() };
+        assert_eq!(check_zone_contiguity(&touching, &placed, &room_zones, &hard), Ok(0.0));
+        assert_eq!(
+            check_zone_contiguity(&apart, &placed, &room_zones, &hard),
+            Err(Violation::ZoneNotContiguous { room_id: "bed2".to_string(), zone: Zone::Private })
+        );
+
+        let soft = SolverConfig { zone_contiguity: vec![constraint(false)], ..Default::default() };
+        assert_eq!(check_zone_contiguity(&touching, &placed, &room_zones, &soft), Ok(2.0));
+        assert_eq!(check_zone_contiguity(&apart, &placed, &room_zones, &soft), Ok(0.0));
+    }
+
+    // Test 3: test_later_room_may_join_zone_parts
+    #[test]
+    fn test_later_room_may_join_zone_parts() {
+        let room_zones = zones(&[("bed1", Zone::Private), ("bed2", Zone::Private), ("bed3", Zone::Private)]);
+        let bed1 = room("bed1", 0.0, 0.0, 3.0, 3.0);
+        let bed3 = room("bed3", 6.0, 0.0, 3.0, 3.0);
+        let hard = SolverConfig {
+            zone_contiguity: vec![ZoneContiguity { zone: Zone::Private, hard: true, weight: 0.0 }],
+            ..Default::default()
+        };
+        let soft = SolverConfig {
+            zone_contiguity: vec![ZoneContiguity { zone: Zone::Private, hard: false, weight: 2.0 }],
+            ..Default::default()
+        };
+
+        // The first room has nothing to touch, and bed3 may start a second part while bed2 is pending
+        assert_eq!(check_zone_contiguity(&bed1, &[], &room_zones, &soft), Ok(0.0));
+        assert_eq!(check_zone_contiguity(&bed3, std::slice::from_ref(&bed1), &room_zones, &hard), Ok(0.0));
+
+        let placed = vec![bed1, bed3];
+        let bridge = room("bed2", 3.0, 0.0, 3.0, 3.0);
+        let elsewhere = room("bed2", 0.0, 5.0, 3.0, 3.0);
+        assert_eq!(check_zone_contiguity(&bridge, &placed, &room_zones, &hard), Ok(0.0));
+        assert_eq!(
+            check_zone_contiguity(&elsewhere, &placed, &room_zones, &hard),
+            Err(Violation::ZoneNotContiguous { room_id: "bed2".to_string(), zone: Zone::Private })
+        );
+    }
+}